@@ -3,10 +3,15 @@
 /// Generates deterministic test vectors for SHA-256, Merkle trees, and block headers.
 /// These vectors ensure parity between Rust, Go, and Python implementations.
 
-use coinjecture_core::hash::sha256;
-use coinjecture_core::merkle::compute_merkle_root;
-use coinjecture_core::codec::compute_header_hash;
+use coinjecture_core::hash::{sha256, HashAlgo};
+use coinjecture_core::merkle::{
+    compute_merkle_root, compute_merkle_root_checked, compute_merkle_root_with_algo, has_mutation,
+};
+use coinjecture_core::codec::{compute_header_hash, compute_header_hash_with_algo};
+use coinjecture_core::filter::{build_block_filter, filter_may_contain};
+use coinjecture_core::pow::{compact_from_target, hash_meets_target, max_target};
 use coinjecture_core::types::BlockHeader;
+use num_bigint::BigUint;
 use serde_json::json;
 
 fn main() {
@@ -187,6 +192,27 @@ fn main() {
         "expected_root": hex::encode(compute_merkle_root(&hundred_hashes))
     }));
 
+    // Vector: CVE-2012-2459 duplicate-transaction malleability. Duplicating
+    // the honest list's last transaction roots to the exact same value,
+    // since the tree's own odd-level padding produces an identical pairing
+    // - `compute_merkle_root_checked`/`has_mutation` must tell the two
+    // apart even though `compute_merkle_root` can't.
+    let malleable_honest = three_hashes.to_vec();
+    let mut malleable_mutated = malleable_honest.clone();
+    malleable_mutated.push(*malleable_honest.last().unwrap());
+    vectors.push(json!({
+        "test_name": "merkle_cve_2012_2459_duplicate_last_tx",
+        "operation": "MERKLE",
+        "honest_tx_hashes": malleable_honest.iter().map(|h| hex::encode(h)).collect::<Vec<_>>(),
+        "mutated_tx_hashes": malleable_mutated.iter().map(|h| hex::encode(h)).collect::<Vec<_>>(),
+        "expected_root": hex::encode(compute_merkle_root(&malleable_honest)),
+        "mutated_root_matches_honest_root": compute_merkle_root(&malleable_mutated) == compute_merkle_root(&malleable_honest),
+        "honest_checked_root": hex::encode(compute_merkle_root_checked(&malleable_honest).unwrap()),
+        "mutated_is_rejected": compute_merkle_root_checked(&malleable_mutated).is_err(),
+        "has_mutation_honest": has_mutation(&malleable_honest),
+        "has_mutation_mutated": has_mutation(&malleable_mutated),
+    }));
+
     // Vector 18: 1000 transactions (large block)
     let thousand_hashes: Vec<[u8; 32]> = (0..1000)
         .map(|i| sha256(format!("tx_{:04}", i).as_bytes()))
@@ -200,6 +226,59 @@ fn main() {
         "expected_root": hex::encode(compute_merkle_root(&thousand_hashes))
     }));
 
+    // Vector: the four-tx tree's sha256 and sha256d roots side by side, so
+    // implementations can validate whichever hashing convention their
+    // target network uses.
+    vectors.push(json!({
+        "test_name": "merkle_four_txs_sha256d",
+        "operation": "MERKLE",
+        "hash_algo": "SHA256D",
+        "tx_hashes": four_hashes.iter().map(|h| hex::encode(h)).collect::<Vec<_>>(),
+        "expected_root": hex::encode(compute_merkle_root_with_algo(&four_hashes, HashAlgo::Sha256d))
+    }));
+
+    // ==================== Merkle Proof Test Vectors ====================
+
+    fn proof_vector(name: &str, leaves: &[[u8; 32]], index: usize) -> serde_json::Value {
+        let root = compute_merkle_root(leaves);
+        let proof = coinjecture_core::merkle::compute_merkle_proof(leaves, index)
+            .expect("index must be in range");
+
+        json!({
+            "test_name": name,
+            "operation": "MERKLE_PROOF",
+            "tx_hashes": leaves.iter().map(|h| hex::encode(h)).collect::<Vec<_>>(),
+            "leaf": hex::encode(leaves[index]),
+            "index": index,
+            "expected_root": hex::encode(root),
+            "siblings": proof.siblings.iter().map(|(sibling, is_left)| json!({
+                "sibling": hex::encode(sibling),
+                "is_left": is_left,
+            })).collect::<Vec<_>>()
+        })
+    }
+
+    // Vector 18b: Single-leaf proof (empty sibling path)
+    vectors.push(proof_vector("merkle_proof_single_tx", &single_hash, 0));
+
+    // Vector 18c: Odd-count proof, proving the duplicated last leaf
+    vectors.push(proof_vector("merkle_proof_three_txs_last_leaf", &three_hashes, 2));
+
+    // Vector 18d: Power-of-two proof, every index
+    for i in 0..eight_hashes.len() {
+        vectors.push(proof_vector(
+            &format!("merkle_proof_eight_txs_index_{}", i),
+            &eight_hashes,
+            i,
+        ));
+    }
+
+    // Vector 18e: Realistic block size, first/middle/last index
+    vectors.push(proof_vector("merkle_proof_hundred_txs_first", &hundred_hashes, 0));
+    vectors.push(proof_vector("merkle_proof_hundred_txs_index_37", &hundred_hashes, 37));
+    vectors.push(proof_vector("merkle_proof_hundred_txs_middle", &hundred_hashes, 50));
+    vectors.push(proof_vector("merkle_proof_hundred_txs_last", &hundred_hashes, 99));
+
     // ==================== Block Header Test Vectors ====================
 
     // Vector 19: Genesis block header
@@ -207,10 +286,10 @@ fn main() {
         codec_version: 1,
         block_index: 0,
         timestamp: 1704067200, // 2024-01-01 00:00:00 UTC
-        parent_hash: [0u8; 32],
-        merkle_root: [0u8; 32],
-        miner_address: [0u8; 32],
-        commitment: [0u8; 32],
+        parent_hash: [0u8; 32].into(),
+        merkle_root: [0u8; 32].into(),
+        miner_address: [0u8; 32].into(),
+        commitment: [0u8; 32].into(),
         difficulty_target: 100,
         nonce: 0,
         extra_data: vec![],
@@ -234,6 +313,29 @@ fn main() {
         "expected_hash": hex::encode(genesis_hash)
     }));
 
+    // Vector: genesis header hashed with sha256d instead of the chain's
+    // single-SHA256 default, so implementations can validate whichever
+    // hashing convention their target network uses.
+    let genesis_hash_sha256d = compute_header_hash_with_algo(&genesis_header, HashAlgo::Sha256d).unwrap();
+    vectors.push(json!({
+        "test_name": "block_header_genesis_sha256d",
+        "operation": "BLOCK_HEADER",
+        "hash_algo": "SHA256D",
+        "header": {
+            "codec_version": genesis_header.codec_version,
+            "block_index": genesis_header.block_index,
+            "timestamp": genesis_header.timestamp,
+            "parent_hash": hex::encode(genesis_header.parent_hash),
+            "merkle_root": hex::encode(genesis_header.merkle_root),
+            "miner_address": hex::encode(genesis_header.miner_address),
+            "commitment": hex::encode(genesis_header.commitment),
+            "difficulty_target": genesis_header.difficulty_target,
+            "nonce": genesis_header.nonce,
+            "extra_data": hex::encode(&genesis_header.extra_data),
+        },
+        "expected_hash": hex::encode(genesis_hash_sha256d)
+    }));
+
     // Vector 20: Block #1 with merkle root
     let block1_merkle = compute_merkle_root(&[sha256(b"tx1"), sha256(b"tx2")]);
     let block1_miner = sha256(b"validator1_pubkey");
@@ -243,8 +345,8 @@ fn main() {
         timestamp: 1704067202,
         parent_hash: genesis_hash,
         merkle_root: block1_merkle,
-        miner_address: block1_miner,
-        commitment: [0xFFu8; 32],
+        miner_address: block1_miner.into(),
+        commitment: [0xFFu8; 32].into(),
         difficulty_target: 100,
         nonce: 42,
         extra_data: vec![],
@@ -273,10 +375,10 @@ fn main() {
         codec_version: 1,
         block_index: 100,
         timestamp: 1704067400,
-        parent_hash: sha256(b"parent_block_99"),
-        merkle_root: sha256(b"merkle_root_100"),
-        miner_address: sha256(b"miner_alice"),
-        commitment: sha256(b"commitment_100"),
+        parent_hash: sha256(b"parent_block_99").into(),
+        merkle_root: sha256(b"merkle_root_100").into(),
+        miner_address: sha256(b"miner_alice").into(),
+        commitment: sha256(b"commitment_100").into(),
         difficulty_target: 1000,
         nonce: 1337,
         extra_data: b"Network B Migration - v4.5.0+".to_vec(),
@@ -305,10 +407,10 @@ fn main() {
         codec_version: 1,
         block_index: 1000,
         timestamp: 1704069200,
-        parent_hash: sha256(b"block_999"),
+        parent_hash: sha256(b"block_999").into(),
         merkle_root: compute_merkle_root(&(0..50).map(|i| sha256(format!("tx_{}", i).as_bytes())).collect::<Vec<_>>()),
-        miner_address: sha256(b"validator_checkpoint"),
-        commitment: sha256(b"checkpoint_1000"),
+        miner_address: sha256(b"validator_checkpoint").into(),
+        commitment: sha256(b"checkpoint_1000").into(),
         difficulty_target: 10000,
         nonce: 999999,
         extra_data: b"CHECKPOINT".to_vec(),
@@ -341,10 +443,10 @@ fn main() {
             codec_version: 1,
             block_index: block_idx,
             timestamp: 1704067200 + (i as i64 * 2),
-            parent_hash: sha256(format!("parent_{}", i).as_bytes()),
-            merkle_root: sha256(format!("merkle_{}", i).as_bytes()),
-            miner_address: sha256(format!("miner_{}", i).as_bytes()),
-            commitment: sha256(format!("commit_{}", i).as_bytes()),
+            parent_hash: sha256(format!("parent_{}", i).as_bytes()).into(),
+            merkle_root: sha256(format!("merkle_{}", i).as_bytes()).into(),
+            miner_address: sha256(format!("miner_{}", i).as_bytes()).into(),
+            commitment: sha256(format!("commit_{}", i).as_bytes()).into(),
             difficulty_target: (i * 100) as u64,
             nonce: nonce_val,
             extra_data: if i % 3 == 0 {
@@ -374,6 +476,104 @@ fn main() {
         }));
     }
 
+    // ==================== Compact Block Filter Test Vectors ====================
+
+    fn filter_vector(name: &str, header: &BlockHeader, tx_hashes: &[[u8; 32]]) -> serde_json::Value {
+        let block_hash = compute_header_hash(header).unwrap();
+        let filter = build_block_filter(header, tx_hashes).unwrap();
+
+        json!({
+            "test_name": name,
+            "operation": "COMPACT_FILTER",
+            "block_hash": hex::encode(block_hash),
+            "tx_hashes": tx_hashes.iter().map(|h| hex::encode(h)).collect::<Vec<_>>(),
+            "commitment": hex::encode(header.commitment),
+            "miner_address": hex::encode(header.miner_address),
+            "filter": hex::encode(&filter),
+            "contains_tx_hashes": tx_hashes
+                .iter()
+                .all(|h| filter_may_contain(&filter, &block_hash, h)),
+        })
+    }
+
+    // Vector: genesis block has no transactions, just the commitment and
+    // miner address as filter elements.
+    vectors.push(filter_vector("compact_filter_genesis", &genesis_header, &[]));
+
+    // Vector: block #1's two transactions.
+    vectors.push(filter_vector(
+        "compact_filter_block_1",
+        &block1_header,
+        &[sha256(b"tx1"), sha256(b"tx2")],
+    ));
+
+    // Vector: realistic transaction count.
+    let filter_hundred_txs: Vec<[u8; 32]> = (0..100)
+        .map(|i: u32| sha256(&i.to_le_bytes()))
+        .collect();
+    vectors.push(filter_vector(
+        "compact_filter_hundred_txs",
+        &block1_header,
+        &filter_hundred_txs,
+    ));
+
+    // ==================== Proof-of-Work Test Vectors ====================
+    //
+    // These exercise the `hash <= target` integer comparison directly
+    // against fabricated (hash, target) pairs rather than a real mined
+    // header, so the exact-equal and one-above boundaries are reachable
+    // without grinding a nonce.
+
+    fn le_bytes_32(value: &BigUint) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        let bytes = value.to_bytes_le();
+        out[..bytes.len()].copy_from_slice(&bytes);
+        out
+    }
+
+    fn pow_vector(name: &str, hash: &[u8; 32], target: &BigUint) -> serde_json::Value {
+        json!({
+            "test_name": name,
+            "operation": "POW",
+            "hash_le": hex::encode(hash),
+            "target_hex": target.to_str_radix(16),
+            "compact_target": format!("{:#010x}", compact_from_target(target)),
+            "meets_target": hash_meets_target(hash, target),
+        })
+    }
+
+    // Vector: hash exactly equal to target.
+    let small_target = BigUint::from(0x1234u32);
+    vectors.push(pow_vector(
+        "pow_hash_exactly_equal_to_target",
+        &le_bytes_32(&small_target),
+        &small_target,
+    ));
+
+    // Vector: hash exactly one above target.
+    vectors.push(pow_vector(
+        "pow_hash_one_above_target",
+        &le_bytes_32(&(small_target.clone() + BigUint::from(1u8))),
+        &small_target,
+    ));
+
+    // Vector: max-target genesis, where the target is the largest value
+    // `validate_pow` will ever accept as a ceiling.
+    let ceiling = max_target();
+    vectors.push(pow_vector(
+        "pow_max_target_genesis",
+        &le_bytes_32(&ceiling),
+        &ceiling,
+    ));
+
+    // Vector: zero target accepts only an all-zero hash.
+    vectors.push(pow_vector("pow_zero_target_zero_hash", &[0u8; 32], &BigUint::from(0u8)));
+    vectors.push(pow_vector(
+        "pow_zero_target_nonzero_hash",
+        &le_bytes_32(&BigUint::from(1u8)),
+        &BigUint::from(0u8),
+    ));
+
     // ==================== Output JSON ====================
 
     let output = json!({
@@ -0,0 +1,237 @@
+/// Golden Vector Verifier for Network B
+///
+/// Reads a golden-vector JSON file as emitted by `generate-vectors`,
+/// recomputes each vector it knows how to check from its recorded inputs,
+/// and reports per-vector pass/fail/skip. Exits nonzero if any vector
+/// disagrees, so a regression in `sha256`, `compute_merkle_root`, or
+/// `compute_header_hash` is caught here in CI instead of only when a
+/// foreign (Go/Python) port disagrees with this crate.
+
+use coinjecture_core::codec::compute_header_hash_with_algo;
+use coinjecture_core::hash::{sha256, HashAlgo};
+use coinjecture_core::merkle::compute_merkle_root_with_algo;
+use coinjecture_core::types::BlockHeader;
+use serde_json::Value;
+use std::process::ExitCode;
+
+enum Outcome {
+    Pass,
+    Fail(String),
+    Skipped(String),
+}
+
+fn decode_hex32(s: &str) -> Option<[u8; 32]> {
+    hex::decode(s).ok()?.try_into().ok()
+}
+
+/// Read a vector's optional `hash_algo` field, defaulting to `Sha256` (the
+/// field is only present on vectors exercising the `sha256d` convention).
+fn hash_algo(vector: &Value) -> Result<HashAlgo, String> {
+    match vector.get("hash_algo").and_then(Value::as_str) {
+        None => Ok(HashAlgo::Sha256),
+        Some("SHA256") => Ok(HashAlgo::Sha256),
+        Some("SHA256D") => Ok(HashAlgo::Sha256d),
+        Some(other) => Err(format!("unknown hash_algo {}", other)),
+    }
+}
+
+fn verify_sha256(vector: &Value) -> Outcome {
+    let Some(input_hex) = vector.get("input_hex").and_then(Value::as_str) else {
+        return Outcome::Skipped("missing input_hex".to_string());
+    };
+    let Some(expected) = vector.get("expected_hash").and_then(Value::as_str) else {
+        return Outcome::Skipped("missing expected_hash".to_string());
+    };
+    let Ok(input) = hex::decode(input_hex) else {
+        return Outcome::Fail("input_hex is not valid hex".to_string());
+    };
+    // Large-input vectors record only a prefix in input_hex plus the full
+    // length in input_size, so their hash can't be recomputed from it.
+    if let Some(size) = vector.get("input_size").and_then(Value::as_u64) {
+        if size != input.len() as u64 {
+            return Outcome::Skipped(format!("input_hex holds {} of {} input bytes", input.len(), size));
+        }
+    }
+
+    let actual = hex::encode(sha256(&input));
+    if actual == expected {
+        Outcome::Pass
+    } else {
+        Outcome::Fail(format!("expected {}, got {}", expected, actual))
+    }
+}
+
+fn verify_merkle(vector: &Value) -> Outcome {
+    let Some(tx_hashes) = vector.get("tx_hashes").and_then(Value::as_array) else {
+        return Outcome::Skipped("no tx_hashes recorded to recompute from".to_string());
+    };
+    let Some(expected) = vector.get("expected_root").and_then(Value::as_str) else {
+        return Outcome::Skipped("missing expected_root".to_string());
+    };
+
+    let algo = match hash_algo(vector) {
+        Ok(algo) => algo,
+        Err(e) => return Outcome::Fail(e),
+    };
+
+    let mut leaves = Vec::with_capacity(tx_hashes.len());
+    for entry in tx_hashes {
+        let Some(s) = entry.as_str() else {
+            return Outcome::Fail("tx_hashes entry is not a string".to_string());
+        };
+        let Some(leaf) = decode_hex32(s) else {
+            return Outcome::Fail(format!("tx hash {} is not 32 bytes of hex", s));
+        };
+        leaves.push(leaf);
+    }
+
+    let actual = hex::encode(compute_merkle_root_with_algo(&leaves, algo));
+    if actual == expected {
+        Outcome::Pass
+    } else {
+        Outcome::Fail(format!("expected {}, got {}", expected, actual))
+    }
+}
+
+fn verify_block_header(vector: &Value) -> Outcome {
+    let Some(header_json) = vector.get("header") else {
+        return Outcome::Skipped("missing header object".to_string());
+    };
+    let Some(expected) = vector.get("expected_hash").and_then(Value::as_str) else {
+        return Outcome::Skipped("missing expected_hash".to_string());
+    };
+
+    let header = match reconstruct_header(header_json) {
+        Ok(header) => header,
+        Err(e) => return Outcome::Fail(e),
+    };
+    let algo = match hash_algo(vector) {
+        Ok(algo) => algo,
+        Err(e) => return Outcome::Fail(e),
+    };
+
+    let actual = match compute_header_hash_with_algo(&header, algo) {
+        Ok(hash) => hex::encode(hash),
+        Err(e) => return Outcome::Fail(format!("compute_header_hash failed: {}", e)),
+    };
+
+    if actual == expected {
+        Outcome::Pass
+    } else {
+        Outcome::Fail(format!("expected {}, got {}", expected, actual))
+    }
+}
+
+/// Rebuild a `BlockHeader` from the nested `header` object a `BLOCK_HEADER`
+/// vector records, hex-decoding each fixed-size field and `extra_data`.
+fn reconstruct_header(header_json: &Value) -> Result<BlockHeader, String> {
+    let field_str = |name: &str| -> Result<&str, String> {
+        header_json
+            .get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("header.{} missing or not a string", name))
+    };
+    let field_u64 = |name: &str| -> Result<u64, String> {
+        header_json
+            .get(name)
+            .and_then(Value::as_u64)
+            .ok_or_else(|| format!("header.{} missing or not a number", name))
+    };
+    let field_hash32 = |name: &str| -> Result<[u8; 32], String> {
+        decode_hex32(field_str(name)?)
+            .ok_or_else(|| format!("header.{} is not 32 bytes of hex", name))
+    };
+
+    let extra_data = hex::decode(field_str("extra_data")?)
+        .map_err(|e| format!("header.extra_data is not valid hex: {}", e))?;
+
+    Ok(BlockHeader {
+        codec_version: field_u64("codec_version")? as u8,
+        block_index: field_u64("block_index")?,
+        timestamp: header_json
+            .get("timestamp")
+            .and_then(Value::as_i64)
+            .ok_or("header.timestamp missing or not a number")?,
+        parent_hash: field_hash32("parent_hash")?.into(),
+        merkle_root: field_hash32("merkle_root")?.into(),
+        miner_address: field_hash32("miner_address")?.into(),
+        commitment: field_hash32("commitment")?.into(),
+        difficulty_target: field_u64("difficulty_target")?,
+        nonce: field_u64("nonce")?,
+        extra_data,
+    })
+}
+
+fn verify_vector(vector: &Value) -> Outcome {
+    match vector.get("operation").and_then(Value::as_str) {
+        Some("SHA256") => verify_sha256(vector),
+        Some("MERKLE") => verify_merkle(vector),
+        Some("BLOCK_HEADER") => verify_block_header(vector),
+        Some(other) => Outcome::Skipped(format!("no verifier registered for operation {}", other)),
+        None => Outcome::Fail("vector has no \"operation\" field".to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: verify-vectors <golden-vectors.json>");
+        return ExitCode::FAILURE;
+    };
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("failed to read {}: {}", path, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let document: Value = match serde_json::from_str(&contents) {
+        Ok(document) => document,
+        Err(e) => {
+            eprintln!("failed to parse {} as JSON: {}", path, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(vectors) = document.get("vectors").and_then(Value::as_array) else {
+        eprintln!("{} has no top-level \"vectors\" array", path);
+        return ExitCode::FAILURE;
+    };
+
+    let (mut passed, mut failed, mut skipped) = (0u32, 0u32, 0u32);
+
+    for vector in vectors {
+        let name = vector.get("test_name").and_then(Value::as_str).unwrap_or("<unnamed>");
+        let operation = vector.get("operation").and_then(Value::as_str).unwrap_or("<unknown>");
+
+        match verify_vector(vector) {
+            Outcome::Pass => {
+                println!("ok   {} ({})", name, operation);
+                passed += 1;
+            }
+            Outcome::Fail(reason) => {
+                println!("FAIL {} ({}): {}", name, operation, reason);
+                failed += 1;
+            }
+            Outcome::Skipped(reason) => {
+                println!("skip {} ({}): {}", name, operation, reason);
+                skipped += 1;
+            }
+        }
+    }
+
+    eprintln!(
+        "\n{} passed, {} failed, {} skipped out of {} vectors",
+        passed,
+        failed,
+        skipped,
+        vectors.len()
+    );
+
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -12,6 +12,6 @@ fuzz_target!(|header: BlockHeader| {
     // Compute hash - should never panic
     if let Ok(hash) = codec::compute_header_hash(&header) {
         // Verify hash is valid
-        assert_eq!(hash.len(), 32, "Hash must be 32 bytes");
+        assert_eq!(hash.as_bytes().len(), 32, "Hash must be 32 bytes");
     }
 });
@@ -0,0 +1,91 @@
+//! Fast-sync checkpoint verification via hashes-of-hashes.
+//!
+//! Lets a node validate long runs of historical headers in one hash instead
+//! of running full SubsetSum PoW verification up to a trusted height. Each
+//! entry in `HASHES_OF_HASHES` is the SHA-256 of the concatenation of
+//! `CHECKPOINT_BATCH_SIZE` consecutive block-header hashes; matching a
+//! batch against its entry vouches for the whole batch at once, letting the
+//! node skip `verify_solution` for every header below the last checkpoint.
+
+use crate::hash::sha256;
+
+/// Number of header hashes hashed together to form one checkpoint entry.
+pub const CHECKPOINT_BATCH_SIZE: usize = 512;
+
+/// Compile-time checkpoints generated from a trusted reference chain via
+/// `build_hashes_of_hashes`. Empty until a release embeds real checkpoints.
+pub const HASHES_OF_HASHES: &[[u8; 32]] = &[];
+
+/// Concatenate a batch of header hashes, in order, and hash the result.
+/// The final partial batch (fewer than `CHECKPOINT_BATCH_SIZE` entries) is
+/// hashed over exactly the hashes present.
+pub fn hash_batch(header_hashes: &[[u8; 32]]) -> [u8; 32] {
+    let mut buf = Vec::with_capacity(header_hashes.len() * 32);
+    for hash in header_hashes {
+        buf.extend_from_slice(hash);
+    }
+    sha256(&buf)
+}
+
+/// Verify that `header_hashes` (a full or final partial batch) matches the
+/// embedded checkpoint at `batch_index`.
+pub fn verify_checkpoint_batch(header_hashes: &[[u8; 32]], batch_index: usize) -> bool {
+    match HASHES_OF_HASHES.get(batch_index) {
+        Some(expected) => &hash_batch(header_hashes) == expected,
+        None => false,
+    }
+}
+
+/// Generate the embedded `HASHES_OF_HASHES` array from a reference chain of
+/// header hashes, splitting it into `CHECKPOINT_BATCH_SIZE`-sized batches.
+pub fn build_hashes_of_hashes(header_hashes: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    header_hashes
+        .chunks(CHECKPOINT_BATCH_SIZE)
+        .map(hash_batch)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_batch_deterministic() {
+        let batch = vec![[1u8; 32], [2u8; 32]];
+        assert_eq!(hash_batch(&batch), hash_batch(&batch));
+    }
+
+    #[test]
+    fn test_hash_batch_order_sensitive() {
+        let forward = vec![[1u8; 32], [2u8; 32]];
+        let reversed = vec![[2u8; 32], [1u8; 32]];
+        assert_ne!(hash_batch(&forward), hash_batch(&reversed));
+    }
+
+    #[test]
+    fn test_build_hashes_of_hashes_splits_into_batches() {
+        let hashes: Vec<[u8; 32]> = (0..(CHECKPOINT_BATCH_SIZE * 2 + 10))
+            .map(|i| {
+                let mut h = [0u8; 32];
+                h[0] = (i % 256) as u8;
+                h[1] = ((i / 256) % 256) as u8;
+                h
+            })
+            .collect();
+
+        let checkpoints = build_hashes_of_hashes(&hashes);
+        assert_eq!(checkpoints.len(), 3); // two full batches + one partial
+
+        assert_eq!(checkpoints[0], hash_batch(&hashes[0..CHECKPOINT_BATCH_SIZE]));
+        assert_eq!(
+            checkpoints[2],
+            hash_batch(&hashes[CHECKPOINT_BATCH_SIZE * 2..])
+        );
+    }
+
+    #[test]
+    fn test_verify_checkpoint_batch_rejects_when_no_checkpoints_embedded() {
+        let batch = vec![[9u8; 32]; CHECKPOINT_BATCH_SIZE];
+        assert!(!verify_checkpoint_batch(&batch, 0));
+    }
+}
@@ -0,0 +1,125 @@
+//! Per-block difficulty retargeting and SubsetSum problem-parameter mapping.
+//!
+//! `BlockHeader::difficulty_target` is a plain integer with no feedback
+//! loop of its own. `retarget` nudges it block-by-block toward
+//! `target_interval_secs`, Ethash-style, and `problem_params_for_target`
+//! turns the resulting integer back into concrete `Problem` generation
+//! parameters (element count and value range) within the bounds
+//! `HardwareTier::element_range` already enforces.
+
+use crate::types::HardwareTier;
+
+/// Floor `retarget` can never go below, so difficulty never collapses to
+/// zero (which would make every `Problem` trivially solvable).
+pub const MIN_DIFFICULTY_TARGET: u64 = 1;
+
+/// Per-block difficulty adjustment, Ethash-style: blocks mined faster than
+/// `target_interval_secs` push the target up (harder), slower blocks push
+/// it down (easier), damped by the `/2048` divisor so no single block
+/// swings difficulty far.
+pub fn retarget(
+    parent_target: u64,
+    parent_timestamp: i64,
+    block_timestamp: i64,
+    target_interval_secs: i64,
+) -> u64 {
+    let target_interval_secs = target_interval_secs.max(1);
+    let elapsed = block_timestamp - parent_timestamp;
+    let sigma = (1 - elapsed / target_interval_secs).max(-99);
+
+    // `parent_target` can legitimately exceed `i64::MAX`, so do the
+    // addition in `i128` rather than casting `parent_target` through
+    // `i64` first - that cast would silently wrap it negative and
+    // collapse difficulty to `MIN_DIFFICULTY_TARGET` instead of raising it.
+    let step = (parent_target / 2048) as i128 * sigma as i128;
+    let new_target = parent_target as i128 + step;
+
+    new_target
+        .clamp(MIN_DIFFICULTY_TARGET as i128, u64::MAX as i128) as u64
+}
+
+/// Map a retargeted difficulty integer onto `Problem` generation
+/// parameters: how many elements the subset-sum instance has, and the
+/// magnitude of each element's value. Element count is scaled
+/// logarithmically with `target` and clamped to `tier.element_range()`, so
+/// a tier's hardware budget is never exceeded regardless of how far
+/// difficulty has climbed.
+pub fn problem_params_for_target(target: u64, tier: HardwareTier) -> (usize, i64) {
+    let (min_elem, max_elem) = tier.element_range();
+
+    let target = target.max(1);
+    let log2_target = (u64::BITS - target.leading_zeros()) as usize;
+    let elem_count = (min_elem + log2_target).min(max_elem);
+
+    let value_range = (target as i64).saturating_mul(1_000).max(1);
+
+    (elem_count, value_range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retarget_raises_difficulty_for_fast_blocks() {
+        let new_target = retarget(1_000_000, 0, 30, 60);
+        assert!(new_target > 1_000_000);
+    }
+
+    #[test]
+    fn test_retarget_lowers_difficulty_for_slow_blocks() {
+        let new_target = retarget(1_000_000, 0, 600, 60);
+        assert!(new_target < 1_000_000);
+    }
+
+    #[test]
+    fn test_retarget_never_underflows_below_floor() {
+        let new_target = retarget(1, 0, 100_000, 60);
+        assert!(new_target >= MIN_DIFFICULTY_TARGET);
+    }
+
+    #[test]
+    fn test_retarget_damping_caps_single_block_swing() {
+        // sigma is clamped to -99, so an arbitrarily long delay can't drop
+        // the target any further than a merely very long one does.
+        let parent_target = 2_048_000;
+        let long_delay = retarget(parent_target, 0, 1_000_000, 1);
+        let absurd_delay = retarget(parent_target, 0, i64::MAX / 2, 1);
+        assert_eq!(long_delay, absurd_delay);
+    }
+
+    #[test]
+    fn test_retarget_raises_difficulty_above_i64_max() {
+        // parent_target beyond i64::MAX used to wrap negative when cast
+        // through i64, collapsing straight to MIN_DIFFICULTY_TARGET instead
+        // of rising for a faster-than-target block.
+        let parent_target = u64::MAX - 1_000;
+        let new_target = retarget(parent_target, 0, 30, 60);
+        assert!(new_target > parent_target);
+    }
+
+    #[test]
+    fn test_retarget_lowers_difficulty_above_i64_max() {
+        let parent_target = u64::MAX - 1_000;
+        let new_target = retarget(parent_target, 0, 600, 60);
+        assert!(new_target < parent_target);
+        assert!(new_target >= MIN_DIFFICULTY_TARGET);
+    }
+
+    #[test]
+    fn test_problem_params_stay_within_tier_bounds() {
+        let (min_elem, max_elem) = HardwareTier::Desktop.element_range();
+        for target in [1u64, 10, 1_000, 1_000_000, u64::MAX] {
+            let (elem_count, value_range) = problem_params_for_target(target, HardwareTier::Desktop);
+            assert!(elem_count >= min_elem && elem_count <= max_elem);
+            assert!(value_range >= 1);
+        }
+    }
+
+    #[test]
+    fn test_problem_params_nondecreasing_with_target() {
+        let (low_count, _) = problem_params_for_target(10, HardwareTier::Server);
+        let (high_count, _) = problem_params_for_target(1_000_000, HardwareTier::Server);
+        assert!(high_count >= low_count);
+    }
+}
@@ -6,6 +6,7 @@
 /// CRITICAL: All functions must be deterministic and match Python PyO3 bindings.
 /// Any divergence will cause consensus forks.
 
+use std::cell::RefCell;
 use std::ffi::CString;
 use std::os::raw::{c_char, c_int, c_uint};
 use std::ptr;
@@ -17,6 +18,27 @@ use crate::merkle::compute_merkle_root;
 use crate::verify::verify_solution;
 use crate::types::{BlockHeader, Problem, Solution, VerifyBudget, ProblemType, HardwareTier};
 
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+/// Record `msg` as the current thread's last FFI error, for later retrieval
+/// via `coinjecture_last_error`. Embedded NUL bytes are stripped first since
+/// `CString::new` rejects them and an error message is never worth failing
+/// over.
+fn set_last_error(msg: impl Into<String>) {
+    let sanitized = msg.into().replace('\0', "");
+    let cstring = CString::new(sanitized).unwrap_or_else(|_| CString::new("").unwrap());
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(cstring));
+}
+
+/// Clear the current thread's last error, so `coinjecture_last_error`
+/// reflects only the outcome of the call in progress rather than a stale
+/// failure from an earlier one.
+fn clear_last_error() {
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+}
+
 /// Result codes for C FFI functions
 #[repr(C)]
 pub enum CoinjResult {
@@ -88,7 +110,10 @@ pub unsafe extern "C" fn coinjecture_sha256_hash(
     input_len: c_uint,
     out_hash: *mut [u8; 32],
 ) -> CoinjResult {
+    clear_last_error();
+
     if input.is_null() || out_hash.is_null() {
+        set_last_error("coinjecture_sha256_hash: input or out_hash pointer is null");
         return CoinjResult::ErrorInvalidInput;
     }
 
@@ -114,7 +139,10 @@ pub unsafe extern "C" fn coinjecture_compute_header_hash(
     header: *const BlockHeaderFFI,
     out_hash: *mut [u8; 32],
 ) -> CoinjResult {
+    clear_last_error();
+
     if header.is_null() || out_hash.is_null() {
+        set_last_error("coinjecture_compute_header_hash: header or out_hash pointer is null");
         return CoinjResult::ErrorInvalidInput;
     }
 
@@ -122,6 +150,7 @@ pub unsafe extern "C" fn coinjecture_compute_header_hash(
 
     // Validate extra_data pointer if len > 0
     if header_ref.extra_data_len > 0 && header_ref.extra_data.is_null() {
+        set_last_error("coinjecture_compute_header_hash: extra_data_len > 0 but extra_data is null");
         return CoinjResult::ErrorInvalidInput;
     }
 
@@ -136,10 +165,10 @@ pub unsafe extern "C" fn coinjecture_compute_header_hash(
         codec_version: header_ref.codec_version as u8,
         block_index: header_ref.block_index as u64,
         timestamp: header_ref.timestamp,
-        parent_hash: header_ref.parent_hash,
-        merkle_root: header_ref.merkle_root,
-        miner_address: header_ref.miner_address,
-        commitment: header_ref.commitment,
+        parent_hash: header_ref.parent_hash.into(),
+        merkle_root: header_ref.merkle_root.into(),
+        miner_address: header_ref.miner_address.into(),
+        commitment: header_ref.commitment.into(),
         difficulty_target: header_ref.difficulty_target as u64,
         nonce: header_ref.nonce,
         extra_data: extra_data.to_vec(),
@@ -147,10 +176,13 @@ pub unsafe extern "C" fn coinjecture_compute_header_hash(
 
     match compute_header_hash(&internal_header) {
         Ok(hash) => {
-            ptr::copy_nonoverlapping(hash.as_ptr(), (*out_hash).as_mut_ptr(), 32);
+            ptr::copy_nonoverlapping(hash.as_bytes().as_ptr(), (*out_hash).as_mut_ptr(), 32);
             CoinjResult::Ok
         }
-        Err(_) => CoinjResult::ErrorEncoding,
+        Err(e) => {
+            set_last_error(format!("coinjecture_compute_header_hash: {}", e));
+            CoinjResult::ErrorEncoding
+        }
     }
 }
 
@@ -170,7 +202,10 @@ pub unsafe extern "C" fn coinjecture_compute_merkle_root(
     tx_count: c_uint,
     out_root: *mut [u8; 32],
 ) -> CoinjResult {
+    clear_last_error();
+
     if out_root.is_null() {
+        set_last_error("coinjecture_compute_merkle_root: out_root pointer is null");
         return CoinjResult::ErrorInvalidInput;
     }
 
@@ -182,7 +217,7 @@ pub unsafe extern "C" fn coinjecture_compute_merkle_root(
 
     let root = compute_merkle_root(&hashes);
 
-    ptr::copy_nonoverlapping(root.as_ptr(), (*out_root).as_mut_ptr(), 32);
+    ptr::copy_nonoverlapping(root.as_bytes().as_ptr(), (*out_root).as_mut_ptr(), 32);
 
     CoinjResult::Ok
 }
@@ -204,7 +239,10 @@ pub unsafe extern "C" fn coinjecture_verify_subset_sum(
     budget: *const VerifyBudgetFFI,
     out_valid: *mut c_int,
 ) -> CoinjResult {
+    clear_last_error();
+
     if problem.is_null() || solution.is_null() || budget.is_null() || out_valid.is_null() {
+        set_last_error("coinjecture_verify_subset_sum: a required pointer is null");
         return CoinjResult::ErrorInvalidInput;
     }
 
@@ -217,6 +255,7 @@ pub unsafe extern "C" fn coinjecture_verify_subset_sum(
         || solution_ref.indices.is_null()
         || problem_ref.elements_len == 0
     {
+        set_last_error("coinjecture_verify_subset_sum: elements/indices pointer is null or elements_len is zero");
         return CoinjResult::ErrorInvalidInput;
     }
 
@@ -234,7 +273,10 @@ pub unsafe extern "C" fn coinjecture_verify_subset_sum(
             2 => HardwareTier::Workstation,
             3 => HardwareTier::Server,
             4 => HardwareTier::Cluster,
-            _ => return CoinjResult::ErrorInvalidInput,
+            other => {
+                set_last_error(format!("coinjecture_verify_subset_sum: unknown tier {}", other));
+                return CoinjResult::ErrorInvalidInput;
+            }
         },
         elements,
         target: problem_ref.target,
@@ -257,10 +299,48 @@ pub unsafe extern "C" fn coinjecture_verify_subset_sum(
             *out_valid = if result.valid { 1 } else { 0 };
             CoinjResult::Ok
         }
-        Err(_) => CoinjResult::ErrorVerificationFailed,
+        Err(e) => {
+            set_last_error(format!("coinjecture_verify_subset_sum: {}", e));
+            CoinjResult::ErrorVerificationFailed
+        }
     }
 }
 
+/// Verify a batch of header hashes against an embedded fast-sync checkpoint
+///
+/// Lets a syncing node validate `count` consecutive header hashes in a
+/// single comparison instead of running full subset-sum PoW verification on
+/// each one, by comparing `sha256(concat(header_hashes))` against the
+/// compiled-in `HASHES_OF_HASHES[batch_index]`.
+///
+/// # Safety
+/// - `header_hashes` must be valid for `count` entries of 32 bytes each
+/// - `out_valid` must point to a valid `c_int`
+///
+/// # Returns
+/// - `CoinjResult::Ok` with `*out_valid` set to 1 if the batch matches, 0 otherwise
+/// - `CoinjResult::ErrorInvalidInput` if pointers are null or `count` is zero
+#[no_mangle]
+pub unsafe extern "C" fn coinjecture_verify_block_batch(
+    header_hashes: *const [u8; 32],
+    count: c_uint,
+    batch_index: c_uint,
+    out_valid: *mut c_int,
+) -> CoinjResult {
+    clear_last_error();
+
+    if header_hashes.is_null() || out_valid.is_null() || count == 0 {
+        set_last_error("coinjecture_verify_block_batch: header_hashes/out_valid pointer is null or count is zero");
+        return CoinjResult::ErrorInvalidInput;
+    }
+
+    let hashes = slice::from_raw_parts(header_hashes, count as usize);
+    let valid = crate::checkpoint::verify_checkpoint_batch(hashes, batch_index as usize);
+
+    *out_valid = if valid { 1 } else { 0 };
+    CoinjResult::Ok
+}
+
 // ==================== Error Handling ====================
 
 /// Get last error message (thread-local)
@@ -270,12 +350,14 @@ pub unsafe extern "C" fn coinjecture_verify_subset_sum(
 ///
 /// # Returns
 /// - Pointer to null-terminated error string
-/// - NULL if no error occurred
+/// - NULL if this thread's most recent error-reporting coinjecture_* call
+///   succeeded, or no such call has been made on it yet
 #[no_mangle]
 pub unsafe extern "C" fn coinjecture_last_error() -> *mut c_char {
-    // TODO: Implement thread-local error storage
-    let error = CString::new("Not implemented").unwrap();
-    error.into_raw()
+    LAST_ERROR.with(|slot| match slot.borrow().as_ref() {
+        Some(message) => message.clone().into_raw(),
+        None => ptr::null_mut(),
+    })
 }
 
 /// Free a string allocated by Rust
@@ -339,4 +421,63 @@ mod tests {
         let codec_version = coinjecture_codec_version();
         assert_eq!(codec_version, 1);
     }
+
+    #[test]
+    fn test_last_error_is_null_before_any_error() {
+        LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+        unsafe {
+            assert!(coinjecture_last_error().is_null());
+        }
+    }
+
+    #[test]
+    fn test_last_error_reports_invalid_input() {
+        unsafe {
+            let result = coinjecture_sha256_hash(ptr::null(), 0, ptr::null_mut());
+            assert!(matches!(result, CoinjResult::ErrorInvalidInput));
+
+            let message = coinjecture_last_error();
+            assert!(!message.is_null());
+            let message = CString::from_raw(message);
+            assert!(message.to_string_lossy().contains("null"));
+        }
+    }
+
+    #[test]
+    fn test_last_error_captures_verification_failure_reason() {
+        let problem = SubsetSumProblemFFI {
+            problem_type: 0,
+            tier: 0,
+            elements: [1i64, 2, 3].as_ptr(),
+            elements_len: 3,
+            target: 100,
+            timestamp: 0,
+        };
+        let solution = SubsetSumSolutionFFI {
+            indices: [0u32].as_ptr(),
+            indices_len: 1,
+            timestamp: 0,
+        };
+        // A budget of zero ops forces verify_solution to hit its op limit
+        // on the very first index.
+        let budget = VerifyBudgetFFI {
+            max_ops: 0,
+            max_duration_ms: 1000,
+            max_memory_bytes: 1_000_000,
+        };
+        let mut valid: c_int = 0;
+
+        unsafe {
+            let result = coinjecture_verify_subset_sum(
+                &problem as *const _,
+                &solution as *const _,
+                &budget as *const _,
+                &mut valid as *mut _,
+            );
+            assert!(matches!(result, CoinjResult::ErrorVerificationFailed));
+
+            let message = CString::from_raw(coinjecture_last_error());
+            assert!(message.to_string_lossy().contains("exceeded budget"));
+        }
+    }
 }
@@ -0,0 +1,416 @@
+//! BIP158-style compact block filters.
+//!
+//! A filter is a probabilistic, no-false-negative membership set over a
+//! block's data elements (transaction hashes, the problem/commitment
+//! value, and the miner address) built as a Golomb-Coded Set. A light
+//! client downloads only the filter, tests its own watched addresses or
+//! transaction hashes against it, and fetches the full block body only on
+//! a match - false positives cost an extra download, false negatives would
+//! silently hide a transaction, so the encoding must never produce one.
+
+use crate::hash::siphash24;
+use crate::hashes::BlockHash;
+use crate::types::BlockHeader;
+
+/// Golomb-Rice parameter: each encoded value's remainder is this many bits.
+const P: u32 = 19;
+/// Target false-positive rate is `1/M`.
+const M: u64 = 784_931;
+
+/// Derive the SipHash key used to hash a block's elements into the filter,
+/// from the block's header hash: the first two little-endian `u64`s of the
+/// hash become the key halves.
+fn derive_key(block_hash: &BlockHash) -> (u64, u64) {
+    let bytes = block_hash.as_bytes();
+    let k0 = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+    let k1 = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+    (k0, k1)
+}
+
+/// Hash `element` under `key` and map it into `[0, n * M)` via the
+/// 128-bit-multiply fast range reduction trick (avoids a modulo bias from
+/// truncating a 64-bit hash into a non-power-of-two range).
+fn hash_to_range(key: (u64, u64), n: u64, element: &[u8]) -> u64 {
+    let hashed = siphash24(key.0, key.1, element);
+    let range = n.saturating_mul(M);
+    ((hashed as u128 * range as u128) >> 64) as u64
+}
+
+/// Write `value` as an unsigned LEB128 varint.
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Read an unsigned LEB128 varint, returning the value and the number of
+/// bytes consumed.
+fn read_varint(data: &[u8]) -> Option<(u64, usize)> {
+    let mut value = 0u64;
+    let mut shift = 0u32;
+    for (i, &byte) in data.iter().enumerate() {
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+        shift += 7;
+        if shift >= 64 {
+            return None;
+        }
+    }
+    None
+}
+
+/// Appends bits MSB-first into a byte buffer, growing it lazily.
+struct BitWriter {
+    bytes: Vec<u8>,
+    bit_pos: u8,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter { bytes: Vec::new(), bit_pos: 0 }
+    }
+
+    fn push_bit(&mut self, bit: bool) {
+        if self.bit_pos == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            let last = self.bytes.last_mut().unwrap();
+            *last |= 1 << (7 - self.bit_pos);
+        }
+        self.bit_pos = (self.bit_pos + 1) % 8;
+    }
+
+    /// Write the low `bits` bits of `value`, most significant first.
+    fn push_bits(&mut self, value: u64, bits: u32) {
+        for i in (0..bits).rev() {
+            self.push_bit((value >> i) & 1 == 1);
+        }
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Reads bits MSB-first out of a byte buffer.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit_pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        BitReader { bytes, bit_pos: 0 }
+    }
+
+    fn next_bit(&mut self) -> Option<bool> {
+        let byte_index = self.bit_pos / 8;
+        let byte = *self.bytes.get(byte_index)?;
+        let bit = (byte >> (7 - (self.bit_pos % 8))) & 1 == 1;
+        self.bit_pos += 1;
+        Some(bit)
+    }
+
+    fn read_unary(&mut self) -> Option<u64> {
+        let mut quotient = 0u64;
+        loop {
+            match self.next_bit()? {
+                true => quotient += 1,
+                false => return Some(quotient),
+            }
+        }
+    }
+
+    fn read_bits(&mut self, bits: u32) -> Option<u64> {
+        let mut value = 0u64;
+        for _ in 0..bits {
+            value = (value << 1) | self.next_bit()? as u64;
+        }
+        Some(value)
+    }
+}
+
+/// Golomb-Rice encode the sorted, deduplicated, delta-coded `values` into a
+/// byte buffer prefixed by two varints: `n`, the *pre-dedup* element count
+/// the filter was built over (needed at query time to re-derive the same
+/// range reduction `build_filter` used), and `values.len()`, the *post-dedup*
+/// entry count (needed to know how many Golomb-Rice entries to read back).
+/// These can differ - two distinct elements can reduce to the same bucket
+/// and collapse to one encoded entry - so they're stored separately rather
+/// than read back from each other.
+fn encode_gcs(n: u64, values: &[u64]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_varint(&mut out, n);
+    write_varint(&mut out, values.len() as u64);
+
+    let mut writer = BitWriter::new();
+    let mut previous = 0u64;
+    for &value in values {
+        let delta = value - previous;
+        previous = value;
+
+        let quotient = delta >> P;
+        let remainder = delta & ((1u64 << P) - 1);
+
+        for _ in 0..quotient {
+            writer.push_bit(true);
+        }
+        writer.push_bit(false);
+        writer.push_bits(remainder, P);
+    }
+
+    out.extend(writer.into_bytes());
+    out
+}
+
+/// Decode a filter and test whether `target` (already hashed and
+/// range-reduced against the filter's own `n`) is present among its
+/// encoded values.
+fn gcs_contains(filter: &[u8], target: u64) -> bool {
+    let Some((_n, n_len)) = read_varint(filter) else {
+        return false;
+    };
+    let Some((count, count_len)) = read_varint(&filter[n_len..]) else {
+        return false;
+    };
+
+    let mut reader = BitReader::new(&filter[n_len + count_len..]);
+    let mut previous = 0u64;
+    for _ in 0..count {
+        let Some(quotient) = reader.read_unary() else {
+            return false;
+        };
+        let Some(remainder) = reader.read_bits(P) else {
+            return false;
+        };
+        let value = previous + ((quotient << P) | remainder);
+        previous = value;
+
+        if value == target {
+            return true;
+        }
+        if value > target {
+            // Values are strictly increasing; once we've passed the
+            // target it can't appear later.
+            return false;
+        }
+    }
+    false
+}
+
+/// Build a compact filter over a block's data elements: its transaction
+/// hashes, its commitment, and its miner address. Identical element sets
+/// always produce byte-identical filters, since reduced values are
+/// deduplicated and sorted before encoding.
+pub fn build_block_filter(header: &BlockHeader, tx_hashes: &[[u8; 32]]) -> Result<Vec<u8>, String> {
+    let block_hash = crate::codec::compute_header_hash(header)?;
+    let mut elements: Vec<&[u8]> = Vec::with_capacity(tx_hashes.len() + 2);
+    elements.extend(tx_hashes.iter().map(|h| h.as_slice()));
+    elements.push(header.commitment.as_ref());
+    elements.push(header.miner_address.as_ref());
+
+    Ok(build_filter(&block_hash, &elements))
+}
+
+/// Build a GCS filter over arbitrary byte-slice elements, keyed to
+/// `block_hash`. Exposed separately from `build_block_filter` so callers
+/// that don't have a full `BlockHeader` (e.g. the unit tests below) can
+/// still exercise the encoding directly.
+pub fn build_filter(block_hash: &BlockHash, elements: &[&[u8]]) -> Vec<u8> {
+    let key = derive_key(block_hash);
+    let n = elements.len() as u64;
+
+    let mut reduced: Vec<u64> = elements
+        .iter()
+        .map(|element| hash_to_range(key, n, element))
+        .collect();
+    reduced.sort_unstable();
+    reduced.dedup();
+
+    encode_gcs(n, &reduced)
+}
+
+/// Test whether `query` may be one of the elements committed to by
+/// `filter`. May return a false positive (roughly 1-in-`M` of the time);
+/// never returns a false negative for an element the filter was actually
+/// built over.
+pub fn filter_may_contain(filter: &[u8], block_hash: &BlockHash, query: &[u8]) -> bool {
+    let Some((n, _)) = read_varint(filter) else {
+        return false;
+    };
+    if n == 0 {
+        return false;
+    }
+
+    let key = derive_key(block_hash);
+    let target = hash_to_range(key, n, query);
+    gcs_contains(filter, target)
+}
+
+/// Test whether any of `queries` may be present in `filter`, short-
+/// circuiting on the first match. Equivalent to, but cheaper than, calling
+/// `filter_may_contain` per query when a caller just wants "does this
+/// block interest me at all" for a set of watched elements.
+pub fn matches_any(filter: &[u8], block_hash: &BlockHash, queries: &[&[u8]]) -> bool {
+    queries.iter().any(|query| filter_may_contain(filter, block_hash, query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::sha256;
+
+    #[test]
+    fn test_filter_contains_every_element_it_was_built_over() {
+        let header = BlockHeader {
+            commitment: sha256(b"commitment").into(),
+            miner_address: sha256(b"miner").into(),
+            ..BlockHeader::default()
+        };
+
+        let tx_hashes = [sha256(b"tx1"), sha256(b"tx2"), sha256(b"tx3")];
+        let block_hash = crate::codec::compute_header_hash(&header).unwrap();
+        let filter = build_block_filter(&header, &tx_hashes).unwrap();
+
+        for tx_hash in &tx_hashes {
+            assert!(filter_may_contain(&filter, &block_hash, tx_hash));
+        }
+        assert!(filter_may_contain(&filter, &block_hash, header.commitment.as_ref()));
+        assert!(filter_may_contain(&filter, &block_hash, header.miner_address.as_ref()));
+    }
+
+    #[test]
+    fn test_filter_rarely_matches_unrelated_query() {
+        let header = BlockHeader {
+            commitment: sha256(b"commitment").into(),
+            miner_address: sha256(b"miner").into(),
+            ..BlockHeader::default()
+        };
+
+        let tx_hashes = [sha256(b"tx1"), sha256(b"tx2")];
+        let block_hash = crate::codec::compute_header_hash(&header).unwrap();
+        let filter = build_block_filter(&header, &tx_hashes).unwrap();
+
+        let false_positives = (0u32..2_000)
+            .filter(|i| {
+                let query = sha256(&i.to_le_bytes());
+                filter_may_contain(&filter, &block_hash, &query)
+            })
+            .count();
+
+        // With M ~= 784931, even one false positive out of 2000 unrelated
+        // queries is unlikely; a stampede of them would mean the encoding
+        // is broken.
+        assert!(false_positives < 20, "unexpectedly high false-positive rate: {}", false_positives);
+    }
+
+    #[test]
+    fn test_identical_element_sets_yield_byte_identical_filters() {
+        let header_a = BlockHeader {
+            commitment: sha256(b"c").into(),
+            miner_address: sha256(b"m").into(),
+            ..BlockHeader::default()
+        };
+        let header_b = header_a.clone();
+
+        let tx_hashes = [sha256(b"tx1"), sha256(b"tx2")];
+        let filter_a = build_block_filter(&header_a, &tx_hashes).unwrap();
+        let filter_b = build_block_filter(&header_b, &tx_hashes).unwrap();
+
+        assert_eq!(filter_a, filter_b);
+    }
+
+    #[test]
+    fn test_colliding_reduced_values_are_deduped_before_encoding() {
+        // Two distinct elements that happen to reduce to the same value (a
+        // filter collision, not a literal duplicate) must still collapse
+        // to one encoded entry - otherwise a zero delta would desync the
+        // Golomb-Rice stream on decode.
+        let block_hash = sha256(b"block").into();
+        let key = derive_key(&block_hash);
+        let n = 2u64;
+
+        let mut seen = std::collections::HashMap::new();
+        let (a, b) = (0u32..10_000)
+            .map(|i| i.to_le_bytes())
+            .find_map(|candidate| {
+                let reduced = hash_to_range(key, n, &candidate);
+                seen.insert(reduced, candidate).map(|prev| (prev, candidate))
+            })
+            .expect("a collision should appear well within 10,000 candidates");
+
+        let filter = build_filter(&block_hash, &[&a, &b]);
+        let (encoded_n, n_len) = read_varint(&filter).unwrap();
+        let (count, _) = read_varint(&filter[n_len..]).unwrap();
+        assert_eq!(encoded_n, 2, "n must stay the pre-dedup element count");
+        assert_eq!(count, 1, "the colliding pair must still collapse to one entry");
+
+        // Regression: querying for either colliding element must still
+        // report a match - encoding/query-time n disagreeing here used to
+        // desync the range reduction and produce a false negative.
+        assert!(filter_may_contain(&filter, &block_hash, &a));
+        assert!(filter_may_contain(&filter, &block_hash, &b));
+    }
+
+    #[test]
+    fn test_matches_any_finds_a_match_among_unrelated_queries() {
+        let header = BlockHeader {
+            commitment: sha256(b"commitment").into(),
+            miner_address: sha256(b"miner").into(),
+            ..BlockHeader::default()
+        };
+
+        let tx_hashes = [sha256(b"tx1"), sha256(b"tx2")];
+        let block_hash = crate::codec::compute_header_hash(&header).unwrap();
+        let filter = build_block_filter(&header, &tx_hashes).unwrap();
+
+        let unrelated = sha256(b"not in the block");
+        let queries: [&[u8]; 2] = [&unrelated, &tx_hashes[1]];
+        assert!(matches_any(&filter, &block_hash, &queries));
+    }
+
+    #[test]
+    fn test_matches_any_is_false_when_nothing_matches() {
+        let header = BlockHeader {
+            commitment: sha256(b"commitment").into(),
+            miner_address: sha256(b"miner").into(),
+            ..BlockHeader::default()
+        };
+
+        let tx_hashes = [sha256(b"tx1")];
+        let block_hash = crate::codec::compute_header_hash(&header).unwrap();
+        let filter = build_block_filter(&header, &tx_hashes).unwrap();
+
+        let a = sha256(b"nope1");
+        let b = sha256(b"nope2");
+        let queries: [&[u8]; 2] = [&a, &b];
+        assert!(!matches_any(&filter, &block_hash, &queries));
+    }
+
+    #[test]
+    fn test_empty_filter_never_matches() {
+        let block_hash = sha256(b"block").into();
+        let filter = build_filter(&block_hash, &[]);
+        assert!(!filter_may_contain(&filter, &block_hash, b"anything"));
+    }
+
+    #[test]
+    fn test_varint_roundtrip() {
+        for value in [0u64, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut buf = Vec::new();
+            write_varint(&mut buf, value);
+            let (decoded, len) = read_varint(&buf).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(len, buf.len());
+        }
+    }
+}
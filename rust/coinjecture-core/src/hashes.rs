@@ -0,0 +1,170 @@
+//! Strongly-typed 32-byte hash wrappers.
+//!
+//! With `BlockHeader`'s hash-shaped fields and the `merkle`/`codec` APIs
+//! that produce them as bare `[u8; 32]`, nothing would stop a caller
+//! passing a parent hash where a merkle root is expected. Following
+//! rust-bitcoin's `BlockHash`/`TxMerkleNode` approach, each type below
+//! wraps a `[u8; 32]` in a distinct newtype, turning that mistake into a
+//! type error. `#[serde(transparent)]` keeps the wire encoding identical to
+//! the plain array each type replaces, so existing golden vectors still
+//! match byte-for-byte.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Declare a 32-byte hash newtype with hex `Display`/`FromStr` and `From`
+/// conversions shared by every wrapper in this module.
+macro_rules! hash_newtype {
+    ($name:ident, $doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(pub [u8; 32]);
+
+        impl $name {
+            /// Wrap a raw 32-byte array.
+            pub const fn from_bytes(bytes: [u8; 32]) -> Self {
+                $name(bytes)
+            }
+
+            /// Borrow the underlying 32-byte array.
+            pub const fn as_bytes(&self) -> &[u8; 32] {
+                &self.0
+            }
+
+            /// Unwrap the underlying 32-byte array.
+            pub const fn to_bytes(self) -> [u8; 32] {
+                self.0
+            }
+        }
+
+        impl From<[u8; 32]> for $name {
+            fn from(bytes: [u8; 32]) -> Self {
+                $name(bytes)
+            }
+        }
+
+        impl From<$name> for [u8; 32] {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                for byte in &self.0 {
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({})", stringify!($name), self)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                // `s.len()` counts bytes, not chars, and the loop below
+                // slices by byte offset assuming one hex char per byte - a
+                // 64-byte string containing multi-byte UTF-8 characters
+                // would pass this check with slice boundaries that land
+                // mid-character, and `&str` indexing panics on those rather
+                // than returning an `Err`. Requiring ASCII up front rules
+                // that out, since every ASCII char is exactly one byte.
+                if !s.is_ascii() || s.len() != 64 {
+                    return Err(format!(
+                        "{} must be 64 hex chars, got {}",
+                        stringify!($name),
+                        s.len()
+                    ));
+                }
+
+                let mut bytes = [0u8; 32];
+                for (i, byte) in bytes.iter_mut().enumerate() {
+                    *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16)
+                        .map_err(|e| format!("invalid hex in {}: {}", stringify!($name), e))?;
+                }
+                Ok($name(bytes))
+            }
+        }
+    };
+}
+
+hash_newtype!(
+    BlockHash,
+    "Hash of a canonically encoded `BlockHeader`, as returned by `codec::compute_header_hash`."
+);
+hash_newtype!(
+    MerkleRoot,
+    "Root of a block's transaction merkle tree, as returned by `merkle::compute_merkle_root`."
+);
+hash_newtype!(TxId, "Hash identifying a transaction, used as a merkle tree leaf.");
+hash_newtype!(
+    MinerAddress,
+    "Address credited with a block's mining reward."
+);
+hash_newtype!(
+    Commitment,
+    "Commitment to a block's proof-of-useful-work problem instance."
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_round_trips_through_from_str() {
+        let hash = BlockHash::from_bytes([0x42; 32]);
+        let parsed: BlockHash = hash.to_string().parse().unwrap();
+        assert_eq!(hash, parsed);
+    }
+
+    #[test]
+    fn test_display_is_lowercase_hex() {
+        let hash = MerkleRoot::from_bytes([0xab; 32]);
+        assert_eq!(hash.to_string(), "ab".repeat(32));
+    }
+
+    #[test]
+    fn test_from_str_rejects_wrong_length() {
+        assert!(TxId::from_str("abcd").is_err());
+    }
+
+    #[test]
+    fn test_from_str_rejects_non_hex() {
+        assert!(MinerAddress::from_str(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_from_str_rejects_multibyte_utf8_instead_of_panicking() {
+        // One 1-byte char plus 21 copies of a 3-byte char is 64 bytes long
+        // but only 22 chars, and the byte-offset slicing in `from_str`
+        // used to panic on the non-char-boundary split instead of
+        // returning this `Err`.
+        let s = format!("a{}", "€".repeat(21));
+        assert_eq!(s.len(), 64);
+        assert!(Commitment::from_str(&s).is_err());
+    }
+
+    #[test]
+    fn test_distinct_types_do_not_compare() {
+        // BlockHash and MerkleRoot wrap the same bytes but are distinct
+        // types - this would fail to compile if they were interchangeable:
+        // `BlockHash::from_bytes([0; 32]) == MerkleRoot::from_bytes([0; 32])`.
+        let block_hash = BlockHash::from_bytes([0; 32]);
+        let merkle_root = MerkleRoot::from_bytes([0; 32]);
+        assert_eq!(block_hash.to_bytes(), merkle_root.to_bytes());
+    }
+}
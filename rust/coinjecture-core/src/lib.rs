@@ -0,0 +1,23 @@
+//! COINjecture core consensus primitives.
+//!
+//! Hashing, block header codec, SubsetSum proof-of-work verification, and
+//! fast-sync checkpoints, exported to Go and Python through the `ffi` layer.
+//! Every function here must be deterministic across platforms - any
+//! divergence between this crate and the Go/Python ports is a consensus
+//! fork.
+
+pub mod checkpoint;
+pub mod codec;
+pub mod difficulty;
+pub mod ffi;
+pub mod filter;
+pub mod hash;
+pub mod hashes;
+pub mod merkle;
+pub mod pow;
+pub mod problem;
+pub mod types;
+pub mod verify;
+
+pub use hashes::*;
+pub use types::*;
@@ -0,0 +1,312 @@
+//! Merkle root computation and inclusion proofs over transaction hashes.
+
+use crate::hash::{sha256, HashAlgo};
+use crate::hashes::MerkleRoot;
+use std::fmt;
+
+/// Compute the merkle root of `leaves`. An empty tree roots to all-zeros; a
+/// single-leaf tree roots to that leaf. Odd levels duplicate the last node
+/// (Bitcoin-style) before pairing, so every implementation agrees on how to
+/// fold an unbalanced tree. Always uses `HashAlgo::Sha256`; see
+/// `compute_merkle_root_with_algo` to select double-SHA256 instead.
+pub fn compute_merkle_root(leaves: &[[u8; 32]]) -> MerkleRoot {
+    compute_merkle_root_with_algo(leaves, HashAlgo::Sha256)
+}
+
+/// Compute the merkle root of `leaves` the same way `compute_merkle_root`
+/// does, but hashing each pair with `algo` instead of being hardwired to
+/// `sha256` - lets a caller targeting a Bitcoin-derived network match its
+/// double-hashing convention.
+pub fn compute_merkle_root_with_algo(leaves: &[[u8; 32]], algo: HashAlgo) -> MerkleRoot {
+    if leaves.is_empty() {
+        return MerkleRoot::from_bytes([0u8; 32]);
+    }
+
+    let mut level: Vec<[u8; 32]> = leaves.to_vec();
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(*level.last().unwrap());
+        }
+
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut buf = Vec::with_capacity(64);
+                buf.extend_from_slice(&pair[0]);
+                buf.extend_from_slice(&pair[1]);
+                algo.hash(&buf)
+            })
+            .collect();
+    }
+
+    MerkleRoot::from_bytes(level[0])
+}
+
+/// Error returned by `compute_merkle_root_checked`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MerkleError {
+    /// Two sibling nodes combined at some level were byte-identical
+    /// without that duplication coming from this function's own
+    /// odd-level padding - the signature of a CVE-2012-2459-style
+    /// injected duplicate transaction.
+    DuplicateTransactions,
+}
+
+impl fmt::Display for MerkleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MerkleError::DuplicateTransactions => {
+                write!(f, "merkle tree contains an injected duplicate transaction")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MerkleError {}
+
+/// Compute the merkle root of `leaves` the same way `compute_merkle_root`
+/// does, but reject trees exhibiting the CVE-2012-2459 duplicate-leaf
+/// malleability: a transaction list mutated by duplicating its last entry
+/// can root to the exact same value as the honest, shorter list, letting
+/// an attacker change a block's transaction count without changing its
+/// header hash. This function walks the same levels `compute_merkle_root`
+/// does, but treats any sibling pair that is byte-identical as suspicious
+/// unless it's the one pair *we* just created by duplicating an odd
+/// level's last node.
+pub fn compute_merkle_root_checked(leaves: &[[u8; 32]]) -> Result<MerkleRoot, MerkleError> {
+    if leaves.is_empty() {
+        return Ok(MerkleRoot::from_bytes([0u8; 32]));
+    }
+
+    let mut level: Vec<[u8; 32]> = leaves.to_vec();
+    while level.len() > 1 {
+        let our_padding = level.len() % 2 == 1;
+        if our_padding {
+            level.push(*level.last().unwrap());
+        }
+
+        let pair_count = level.len() / 2;
+        for (i, pair) in level.chunks(2).enumerate() {
+            let is_our_synthetic_pair = our_padding && i == pair_count - 1;
+            if !is_our_synthetic_pair && pair[0] == pair[1] {
+                return Err(MerkleError::DuplicateTransactions);
+            }
+        }
+
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut buf = Vec::with_capacity(64);
+                buf.extend_from_slice(&pair[0]);
+                buf.extend_from_slice(&pair[1]);
+                sha256(&buf)
+            })
+            .collect();
+    }
+
+    Ok(MerkleRoot::from_bytes(level[0]))
+}
+
+/// Does `leaves` exhibit the CVE-2012-2459 duplicate-transaction
+/// malleability, i.e. would `compute_merkle_root_checked` reject it?
+pub fn has_mutation(leaves: &[[u8; 32]]) -> bool {
+    compute_merkle_root_checked(leaves).is_err()
+}
+
+/// An inclusion proof for one leaf: the sibling hash at each level from the
+/// leaf up to the root, paired with whether that sibling sits on the left
+/// (`true`) or right (`false`) of the node being folded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub siblings: Vec<([u8; 32], bool)>,
+}
+
+/// Build an inclusion proof for `leaves[index]`, mirroring
+/// `compute_merkle_root`'s odd-level last-node duplication exactly so a
+/// proof generated here always verifies against that function's root.
+pub fn compute_merkle_proof(leaves: &[[u8; 32]], index: usize) -> Option<MerkleProof> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut siblings = Vec::new();
+    let mut level: Vec<[u8; 32]> = leaves.to_vec();
+    let mut position = index;
+
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(*level.last().unwrap());
+        }
+
+        let sibling_index = position ^ 1;
+        let sibling_is_left = sibling_index < position;
+        siblings.push((level[sibling_index], sibling_is_left));
+
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut buf = Vec::with_capacity(64);
+                buf.extend_from_slice(&pair[0]);
+                buf.extend_from_slice(&pair[1]);
+                sha256(&buf)
+            })
+            .collect();
+
+        position /= 2;
+    }
+
+    Some(MerkleProof { siblings })
+}
+
+/// Verify that `leaf` is included under `root`, by folding it with each
+/// sibling in `proof` (left siblings go first, right siblings second,
+/// exactly as `compute_merkle_root` pairs nodes) and comparing the result.
+pub fn verify_merkle_proof(root: &MerkleRoot, leaf: &[u8; 32], proof: &MerkleProof) -> bool {
+    let mut current = *leaf;
+
+    for (sibling, sibling_is_left) in &proof.siblings {
+        let mut buf = Vec::with_capacity(64);
+        if *sibling_is_left {
+            buf.extend_from_slice(sibling);
+            buf.extend_from_slice(&current);
+        } else {
+            buf.extend_from_slice(&current);
+            buf.extend_from_slice(sibling);
+        }
+        current = sha256(&buf);
+    }
+
+    current == *root.as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_root_is_zero() {
+        assert_eq!(*compute_merkle_root(&[]).as_bytes(), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_single_leaf_roots_to_itself() {
+        let leaf = [0x42u8; 32];
+        assert_eq!(*compute_merkle_root(&[leaf]).as_bytes(), leaf);
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let leaves = [sha256(b"a"), sha256(b"b"), sha256(b"c")];
+        assert_eq!(compute_merkle_root(&leaves), compute_merkle_root(&leaves));
+    }
+
+    #[test]
+    fn test_proof_verifies_every_index_even_count() {
+        let leaves = [sha256(b"a"), sha256(b"b"), sha256(b"c"), sha256(b"d")];
+        let root = compute_merkle_root(&leaves);
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = compute_merkle_proof(&leaves, i).expect("index in range");
+            assert!(verify_merkle_proof(&root, leaf, &proof), "proof failed for index {}", i);
+        }
+    }
+
+    #[test]
+    fn test_proof_verifies_every_index_odd_count() {
+        let leaves = [sha256(b"a"), sha256(b"b"), sha256(b"c")];
+        let root = compute_merkle_root(&leaves);
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = compute_merkle_proof(&leaves, i).expect("index in range");
+            assert!(verify_merkle_proof(&root, leaf, &proof), "proof failed for index {}", i);
+        }
+    }
+
+    #[test]
+    fn test_proof_rejects_mutated_leaf() {
+        let leaves = [sha256(b"a"), sha256(b"b"), sha256(b"c"), sha256(b"d")];
+        let root = compute_merkle_root(&leaves);
+        let proof = compute_merkle_proof(&leaves, 1).expect("index in range");
+
+        assert!(!verify_merkle_proof(&root, &sha256(b"mutated"), &proof));
+    }
+
+    #[test]
+    fn test_compute_merkle_proof_out_of_bounds_returns_none() {
+        let leaves = [sha256(b"a")];
+        assert!(compute_merkle_proof(&leaves, 1).is_none());
+    }
+
+    #[test]
+    fn test_single_leaf_proof_is_empty_and_verifies() {
+        let leaf = sha256(b"solo");
+        let leaves = [leaf];
+        let proof = compute_merkle_proof(&leaves, 0).unwrap();
+
+        assert!(proof.siblings.is_empty());
+        assert!(verify_merkle_proof(&MerkleRoot::from_bytes(leaf), &leaf, &proof));
+    }
+
+    #[test]
+    fn test_compute_merkle_root_with_algo_defaults_match_compute_merkle_root() {
+        let leaves = [sha256(b"a"), sha256(b"b"), sha256(b"c")];
+        assert_eq!(
+            compute_merkle_root_with_algo(&leaves, HashAlgo::Sha256),
+            compute_merkle_root(&leaves)
+        );
+    }
+
+    #[test]
+    fn test_compute_merkle_root_with_algo_sha256d_differs_from_sha256() {
+        let leaves = [sha256(b"a"), sha256(b"b"), sha256(b"c"), sha256(b"d")];
+        assert_ne!(
+            compute_merkle_root_with_algo(&leaves, HashAlgo::Sha256d),
+            compute_merkle_root_with_algo(&leaves, HashAlgo::Sha256)
+        );
+    }
+
+    #[test]
+    fn test_checked_accepts_honest_odd_count_tree() {
+        let leaves = [sha256(b"a"), sha256(b"b"), sha256(b"c")];
+        assert_eq!(
+            compute_merkle_root_checked(&leaves).unwrap(),
+            compute_merkle_root(&leaves)
+        );
+        assert!(!has_mutation(&leaves));
+    }
+
+    #[test]
+    fn test_checked_rejects_duplicated_last_transaction() {
+        // The classic CVE-2012-2459 mutation: duplicating the list's last
+        // transaction makes an odd count even, but the honest-tree's own
+        // last-node padding produces the exact same pairing, so the root
+        // is unchanged even though the transaction list now differs.
+        let honest = [sha256(b"a"), sha256(b"b"), sha256(b"c")];
+        let mut mutated = honest.to_vec();
+        mutated.push(*honest.last().unwrap());
+
+        assert_eq!(compute_merkle_root(&honest), compute_merkle_root(&mutated));
+        assert_eq!(
+            compute_merkle_root_checked(&mutated),
+            Err(MerkleError::DuplicateTransactions)
+        );
+        assert!(has_mutation(&mutated));
+    }
+
+    #[test]
+    fn test_checked_rejects_duplicate_pair_at_a_deeper_level() {
+        // No two sibling leaves are equal, but the repeated subtree makes
+        // the first hashed level two identical nodes `hash(a || b)` - just
+        // as much a collision signature, even though the leaf count itself
+        // is already even.
+        let a = sha256(b"a");
+        let b = sha256(b"b");
+        let leaves = [a, b, a, b];
+
+        assert_eq!(
+            compute_merkle_root_checked(&leaves),
+            Err(MerkleError::DuplicateTransactions)
+        );
+        assert!(has_mutation(&leaves));
+    }
+}
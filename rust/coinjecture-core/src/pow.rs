@@ -0,0 +1,248 @@
+//! Compact ("nBits") difficulty target encoding and SPV proof-of-work
+//! validation, Bitcoin-style.
+//!
+//! A target is stored compactly as a 32-bit float-like encoding: the high
+//! byte is an exponent `e`, the low 3 bytes are a mantissa `m`, and the
+//! full 256-bit target is `m * 256^(e-3)`. `BlockHeader::difficulty_target`
+//! carries this compact form in its low 32 bits (the remaining bits are
+//! reserved for a future wider encoding). `validate_pow` decodes it,
+//! rejects anything Bitcoin's consensus rules would also reject, and
+//! checks the header's hash against it as an SPV client would, without
+//! needing the rest of the block.
+
+use std::fmt;
+
+use num_bigint::BigUint;
+
+use crate::codec::compute_header_hash;
+use crate::types::BlockHeader;
+
+/// A 32-bit compact difficulty target ("nBits"): the high byte is an
+/// exponent, the low 3 bytes are a mantissa.
+pub type CompactTarget = u32;
+
+/// Mantissa sign bit (bit 23). Bitcoin reserves this to mean "negative"
+/// and never accepts it; `target_from_compact`/`validate_pow` reject it.
+const MANTISSA_SIGN_BIT: u32 = 0x0080_0000;
+const MANTISSA_MASK: u32 = 0x007f_ffff;
+
+/// Largest target this chain will ever accept. Chosen to match Bitcoin's
+/// own ceiling (`2^224 - 1`, whose nearest compact encoding is
+/// `0x1d00ffff`), so a header can't claim an absurdly high target to
+/// make any hash pass `validate_pow`.
+pub fn max_target() -> BigUint {
+    (BigUint::from(1u8) << 224u32) - BigUint::from(1u8)
+}
+
+/// Error returned by `validate_pow` and the compact-target codec.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PowError {
+    /// The compact target's mantissa had its sign bit set.
+    NegativeMantissa,
+    /// The decoded target exceeds `max_target()`.
+    TargetTooHigh,
+    /// The header could not be canonically encoded to hash.
+    HashFailed(String),
+    /// The header's hash, read as a little-endian 256-bit integer,
+    /// exceeds its claimed target.
+    HashExceedsTarget,
+}
+
+impl fmt::Display for PowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PowError::NegativeMantissa => write!(f, "compact target mantissa has the sign bit set"),
+            PowError::TargetTooHigh => write!(f, "decoded target exceeds max_target"),
+            PowError::HashFailed(e) => write!(f, "failed to hash header: {}", e),
+            PowError::HashExceedsTarget => write!(f, "header hash exceeds its claimed target"),
+        }
+    }
+}
+
+impl std::error::Error for PowError {}
+
+/// Decode a compact target into its full 256-bit value:
+/// `target = mantissa * 256^(exponent - 3)`.
+///
+/// Returns `Err(PowError::NegativeMantissa)` if the mantissa's sign bit is
+/// set, matching Bitcoin's own compact-encoding rule.
+pub fn target_from_compact(compact: CompactTarget) -> Result<BigUint, PowError> {
+    if compact & MANTISSA_SIGN_BIT != 0 {
+        return Err(PowError::NegativeMantissa);
+    }
+
+    let exponent = (compact >> 24) as i32;
+    let mantissa = BigUint::from(compact & MANTISSA_MASK);
+
+    let shift = (exponent - 3) * 8;
+    let target = if shift >= 0 {
+        mantissa << shift as usize
+    } else {
+        mantissa >> (-shift) as usize
+    };
+
+    Ok(target)
+}
+
+/// Encode a full 256-bit target as its compact form, the inverse of
+/// `target_from_compact`. A zero target encodes to `0`.
+pub fn compact_from_target(target: &BigUint) -> CompactTarget {
+    let mut bytes = target.to_bytes_be();
+    if bytes == [0u8] {
+        bytes.clear();
+    }
+
+    let mut size = bytes.len();
+    let mut mantissa: u32 = match size {
+        0 => 0,
+        1 => (bytes[0] as u32) << 16,
+        2 => ((bytes[0] as u32) << 16) | ((bytes[1] as u32) << 8),
+        _ => ((bytes[0] as u32) << 16) | ((bytes[1] as u32) << 8) | (bytes[2] as u32),
+    };
+
+    // If the mantissa's top bit would be set, it would be misread as the
+    // sign bit, so shift one byte right and grow the exponent to compensate.
+    if mantissa & MANTISSA_SIGN_BIT != 0 {
+        mantissa >>= 8;
+        size += 1;
+    }
+
+    ((size as u32) << 24) | mantissa
+}
+
+/// Does `hash`, read as a little-endian 256-bit integer, meet `target`
+/// (i.e. `hash <= target`)? The comparison primitive `validate_pow` and
+/// the golden vectors both exercise, kept separate from header hashing so
+/// boundary cases (hash exactly at the target, one above it) can be
+/// tested directly rather than by grinding a real proof of work.
+pub fn hash_meets_target(hash: &[u8; 32], target: &BigUint) -> bool {
+    BigUint::from_bytes_le(hash) <= *target
+}
+
+/// Validate that `header`'s hash meets the difficulty target encoded in
+/// its low 32 bits, the way an SPV client checks a header without the
+/// rest of the block: decode the compact target, reject it outright if
+/// it's malformed or above `max_target()`, then require
+/// `hash(header) <= target` with the hash read as a little-endian 256-bit
+/// integer.
+pub fn validate_pow(header: &BlockHeader) -> Result<(), PowError> {
+    let compact = header.difficulty_target as CompactTarget;
+    let target = target_from_compact(compact)?;
+
+    if target > max_target() {
+        return Err(PowError::TargetTooHigh);
+    }
+
+    let hash = compute_header_hash(header).map_err(PowError::HashFailed)?;
+
+    if hash_meets_target(hash.as_bytes(), &target) {
+        Ok(())
+    } else {
+        Err(PowError::HashExceedsTarget)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_from_compact_rejects_sign_bit() {
+        assert_eq!(
+            target_from_compact(0x01800000),
+            Err(PowError::NegativeMantissa)
+        );
+    }
+
+    #[test]
+    fn test_target_from_compact_matches_known_bitcoin_vector() {
+        // Bitcoin genesis block's nBits 0x1d00ffff decodes to
+        // 0x00000000FFFF0000000000000000000000000000000000000000000000000000.
+        let target = target_from_compact(0x1d00ffff).unwrap();
+        let expected = BigUint::parse_bytes(
+            b"00000000FFFF0000000000000000000000000000000000000000000000000000",
+            16,
+        )
+        .unwrap();
+        assert_eq!(target, expected);
+    }
+
+    #[test]
+    fn test_compact_from_target_round_trips_through_decode() {
+        for compact in [0x1d00ffffu32, 0x04123456, 0x02008000, 0x1b0404cb] {
+            let target = target_from_compact(compact).unwrap();
+            let re_encoded = compact_from_target(&target);
+            let re_decoded = target_from_compact(re_encoded).unwrap();
+            assert_eq!(re_decoded, target, "round trip mismatch for {:#x}", compact);
+        }
+    }
+
+    #[test]
+    fn test_zero_target_round_trips() {
+        let zero = BigUint::from(0u8);
+        assert_eq!(compact_from_target(&zero), 0);
+        assert_eq!(target_from_compact(0).unwrap(), zero);
+    }
+
+    fn header_with_difficulty_target(difficulty_target: u64) -> BlockHeader {
+        BlockHeader {
+            difficulty_target,
+            ..BlockHeader::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_pow_rejects_target_above_max() {
+        // Exponent 0x21 with mantissa 0xffff puts the target well
+        // above max_target()'s 2^224 - 1 ceiling.
+        let header = header_with_difficulty_target(0x2100ffff);
+        assert_eq!(validate_pow(&header), Err(PowError::TargetTooHigh));
+    }
+
+    #[test]
+    fn test_validate_pow_rejects_negative_mantissa() {
+        let header = header_with_difficulty_target(0x01800000);
+        assert_eq!(validate_pow(&header), Err(PowError::NegativeMantissa));
+    }
+
+    #[test]
+    fn test_validate_pow_accepts_max_target_as_valid_ceiling() {
+        // max_target() is the largest target validate_pow ever accepts in
+        // principle; encoding and decoding it must not itself be rejected
+        // as "too high" (whether the header's actual hash clears it is a
+        // separate, hash-dependent question covered by hash_meets_target).
+        let target = max_target();
+        let compact = compact_from_target(&target);
+        let header = header_with_difficulty_target(compact as u64);
+        assert_ne!(validate_pow(&header), Err(PowError::TargetTooHigh));
+    }
+
+    #[test]
+    fn test_hash_meets_target_accepts_hash_exactly_equal_to_target() {
+        let target = BigUint::from(0x1234u32);
+        let hash = le_hash_for(&target);
+        assert!(hash_meets_target(&hash, &target));
+    }
+
+    #[test]
+    fn test_hash_meets_target_rejects_hash_one_above_target() {
+        let target = BigUint::from(0x1234u32);
+        let hash = le_hash_for(&(target.clone() + BigUint::from(1u8)));
+        assert!(!hash_meets_target(&hash, &target));
+    }
+
+    #[test]
+    fn test_hash_meets_target_accepts_zero_against_zero_target() {
+        let target = BigUint::from(0u8);
+        assert!(hash_meets_target(&[0u8; 32], &target));
+    }
+
+    /// Encode `value` as a little-endian 32-byte hash array, the inverse
+    /// of `hash_meets_target`'s `BigUint::from_bytes_le`.
+    fn le_hash_for(value: &BigUint) -> [u8; 32] {
+        let mut hash = [0u8; 32];
+        let bytes = value.to_bytes_le();
+        hash[..bytes.len()].copy_from_slice(&bytes);
+        hash
+    }
+}
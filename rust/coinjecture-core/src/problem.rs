@@ -0,0 +1,473 @@
+//! Pluggable proof-of-useful-work problem families.
+//!
+//! Mining was originally hard-coded to SubsetSum (see `verify::verify_solution`).
+//! `ProofOfUsefulWork` is the interface every family implements instead: generate
+//! an instance sized for a hardware tier and difficulty, verify a candidate
+//! solution within a `VerifyBudget`, and canonically encode an instance so its
+//! hash can be committed to by a block. `ProblemType::discriminant` is encoded
+//! as the first byte of every `canonical_encode` output, so the family a block
+//! used is bound into the same commitment as the instance data.
+//!
+//! Each family reuses the existing `Problem`/`Solution` envelope rather than
+//! introducing per-family types, interpreting `elements`/`target`/`indices`
+//! differently:
+//! - `SubsetSumPow`: `elements` are the set, `target` is the sum to hit,
+//!   `indices` are the chosen elements (unchanged from `verify::verify_solution`).
+//! - `KnapsackPow`: `elements` is `[capacity, w0, v0, w1, v1, ...]`, `target` is
+//!   the value threshold to meet or beat, `indices` are chosen item numbers.
+//! - `HamiltonianPow`: `elements` is `[node_count, u0, v0, w0, u1, v1, w1, ...]`
+//!   (an edge list), `target` is the maximum total path weight, `indices` is
+//!   the visiting order (a permutation of `0..node_count`).
+
+use crate::hash::siphash24;
+use crate::types::{HardwareTier, Problem, ProblemType, Solution, VerifyBudget, VerifyResult};
+use std::collections::HashSet;
+use std::time::Instant;
+
+/// A proof-of-useful-work problem family.
+pub trait ProofOfUsefulWork {
+    /// This family's stable `ProblemType` discriminant.
+    fn problem_type() -> ProblemType;
+
+    /// Generate a fresh, solvable instance for `tier`, sized by
+    /// `difficulty_params` (element count, value range) as produced by
+    /// `difficulty::problem_params_for_target`. `seed` makes generation
+    /// deterministic for a given set of parameters.
+    fn generate(tier: HardwareTier, difficulty_params: (usize, i64), seed: u64, timestamp: i64) -> Problem;
+
+    /// Verify `solution` against `problem`, aborting as soon as `budget`
+    /// would be exceeded rather than completing an over-budget check.
+    fn verify(problem: &Problem, solution: &Solution, budget: &VerifyBudget) -> Result<VerifyResult, String>;
+
+    /// Canonical byte encoding of `problem`, fed into the block commitment.
+    /// Always begins with `problem.problem_type.discriminant()`.
+    fn canonical_encode(problem: &Problem) -> Vec<u8>;
+}
+
+/// Deterministic pseudorandom i64 stream: siphash24 keyed by `seed`, indexed
+/// by an incrementing counter, so the same `(seed, index)` always reproduces
+/// the same value.
+fn pseudorandom_i64(seed: u64, index: u64, value_range: i64) -> i64 {
+    let raw = siphash24(seed, index, &index.to_le_bytes());
+    let range = value_range.max(1) as u64;
+    (raw % range) as i64
+}
+
+/// Shared `canonical_encode` body: every family's `elements`/`target` are
+/// laid out as little-endian integers behind the family's discriminant
+/// byte, whatever that family's `elements` layout means internally.
+fn encode_elements_and_target(problem: &Problem) -> Vec<u8> {
+    let mut out = vec![problem.problem_type.discriminant()];
+    for element in &problem.elements {
+        out.extend_from_slice(&element.to_le_bytes());
+    }
+    out.extend_from_slice(&problem.target.to_le_bytes());
+    out
+}
+
+/// A verification-budget tracker shared by every `ProofOfUsefulWork::verify`
+/// implementation: counts ops, estimates memory as a fixed cost per
+/// tracked item, and samples wall-clock time, aborting on the first budget
+/// any step would exceed.
+struct BudgetTracker<'a> {
+    budget: &'a VerifyBudget,
+    ops_used: u64,
+    memory_used: u64,
+    started: Instant,
+}
+
+impl<'a> BudgetTracker<'a> {
+    fn new(budget: &'a VerifyBudget) -> Self {
+        BudgetTracker {
+            budget,
+            ops_used: 0,
+            memory_used: 0,
+            started: Instant::now(),
+        }
+    }
+
+    /// Charge `ops` operations and `memory_bytes` toward the budget,
+    /// returning an error the instant any of the three limits is crossed.
+    fn charge(&mut self, ops: u64, memory_bytes: u64) -> Result<(), String> {
+        self.ops_used = self.ops_used.saturating_add(ops);
+        self.memory_used = self.memory_used.saturating_add(memory_bytes);
+
+        if self.ops_used > self.budget.max_ops {
+            return Err(format!(
+                "verification exceeded op budget: {} > {}",
+                self.ops_used, self.budget.max_ops
+            ));
+        }
+        if self.memory_used > self.budget.max_memory_bytes {
+            return Err(format!(
+                "verification exceeded memory budget: {} > {}",
+                self.memory_used, self.budget.max_memory_bytes
+            ));
+        }
+        let elapsed_ms = self.started.elapsed().as_millis() as u64;
+        if elapsed_ms > self.budget.max_duration_ms {
+            return Err(format!(
+                "verification exceeded time budget: {}ms > {}ms",
+                elapsed_ms, self.budget.max_duration_ms
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The original family: does a subset of `elements` sum to `target`.
+pub struct SubsetSumPow;
+
+impl ProofOfUsefulWork for SubsetSumPow {
+    fn problem_type() -> ProblemType {
+        ProblemType::SubsetSum
+    }
+
+    fn generate(tier: HardwareTier, difficulty_params: (usize, i64), seed: u64, timestamp: i64) -> Problem {
+        let (element_count, value_range) = difficulty_params;
+        let elements: Vec<i64> = (0..element_count as u64)
+            .map(|i| pseudorandom_i64(seed, i, value_range) + 1)
+            .collect();
+
+        // Guarantee solvability by targeting the sum of roughly half the
+        // generated elements.
+        let target: i64 = elements.iter().take(element_count / 2).sum();
+
+        Problem {
+            problem_type: ProblemType::SubsetSum,
+            tier,
+            elements,
+            target,
+            timestamp,
+        }
+    }
+
+    fn verify(problem: &Problem, solution: &Solution, budget: &VerifyBudget) -> Result<VerifyResult, String> {
+        crate::verify::verify_solution(problem, solution, budget)
+    }
+
+    fn canonical_encode(problem: &Problem) -> Vec<u8> {
+        encode_elements_and_target(problem)
+    }
+}
+
+/// 0/1 Knapsack as a decision problem: does some subset of items, each with
+/// a weight and a value, fit within `capacity` and reach at least `target`
+/// in total value? `problem.elements` is `[capacity, w0, v0, w1, v1, ...]`;
+/// `solution.indices` names chosen item numbers (0-based into the w/v pairs).
+pub struct KnapsackPow;
+
+impl KnapsackPow {
+    fn capacity(problem: &Problem) -> Option<i64> {
+        problem.elements.first().copied()
+    }
+
+    fn item(problem: &Problem, item_index: u32) -> Option<(i64, i64)> {
+        let base = 1 + item_index as usize * 2;
+        let weight = *problem.elements.get(base)?;
+        let value = *problem.elements.get(base + 1)?;
+        Some((weight, value))
+    }
+}
+
+impl ProofOfUsefulWork for KnapsackPow {
+    fn problem_type() -> ProblemType {
+        ProblemType::Knapsack
+    }
+
+    fn generate(tier: HardwareTier, difficulty_params: (usize, i64), seed: u64, timestamp: i64) -> Problem {
+        let (item_count, value_range) = difficulty_params;
+        let mut elements = Vec::with_capacity(1 + item_count * 2);
+
+        let mut items = Vec::with_capacity(item_count);
+        for i in 0..item_count as u64 {
+            let weight = pseudorandom_i64(seed, i * 2, value_range) + 1;
+            let value = pseudorandom_i64(seed, i * 2 + 1, value_range) + 1;
+            items.push((weight, value));
+        }
+
+        // Guarantee solvability: size the capacity and target off the first
+        // half of the generated items.
+        let take = item_count / 2;
+        let capacity: i64 = items.iter().take(take).map(|(w, _)| w).sum::<i64>().max(1);
+        let target: i64 = items.iter().take(take).map(|(_, v)| v).sum();
+
+        elements.push(capacity);
+        for (weight, value) in &items {
+            elements.push(*weight);
+            elements.push(*value);
+        }
+
+        Problem {
+            problem_type: ProblemType::Knapsack,
+            tier,
+            elements,
+            target,
+            timestamp,
+        }
+    }
+
+    fn verify(problem: &Problem, solution: &Solution, budget: &VerifyBudget) -> Result<VerifyResult, String> {
+        let mut tracker = BudgetTracker::new(budget);
+
+        let Some(capacity) = Self::capacity(problem) else {
+            return Ok(VerifyResult { valid: false, ops_used: 0 });
+        };
+
+        if solution.indices.is_empty() {
+            return Ok(VerifyResult { valid: false, ops_used: 0 });
+        }
+
+        let mut seen = HashSet::with_capacity(solution.indices.len());
+        let mut total_weight: i64 = 0;
+        let mut total_value: i64 = 0;
+
+        for &item_index in &solution.indices {
+            tracker.charge(1, 16)?;
+
+            if !seen.insert(item_index) {
+                return Ok(VerifyResult { valid: false, ops_used: tracker.ops_used });
+            }
+
+            let Some((weight, value)) = Self::item(problem, item_index) else {
+                return Ok(VerifyResult { valid: false, ops_used: tracker.ops_used });
+            };
+
+            total_weight = match total_weight.checked_add(weight) {
+                Some(w) => w,
+                None => return Ok(VerifyResult { valid: false, ops_used: tracker.ops_used }),
+            };
+            total_value = match total_value.checked_add(value) {
+                Some(v) => v,
+                None => return Ok(VerifyResult { valid: false, ops_used: tracker.ops_used }),
+            };
+        }
+
+        Ok(VerifyResult {
+            valid: total_weight <= capacity && total_value >= problem.target,
+            ops_used: tracker.ops_used,
+        })
+    }
+
+    fn canonical_encode(problem: &Problem) -> Vec<u8> {
+        encode_elements_and_target(problem)
+    }
+}
+
+/// Hamiltonian-path-under-threshold: does a path visiting every node exactly
+/// once, following only edges present in the graph, cost at most `target`?
+/// `problem.elements` is `[node_count, u0, v0, w0, u1, v1, w1, ...]` (an
+/// undirected edge list); `solution.indices` is the candidate visiting
+/// order, a permutation of `0..node_count`.
+pub struct HamiltonianPow;
+
+impl HamiltonianPow {
+    fn node_count(problem: &Problem) -> Option<u32> {
+        Some((*problem.elements.first()?) as u32)
+    }
+
+    fn edge_weight(problem: &Problem, from: u32, to: u32) -> Option<i64> {
+        let edges = &problem.elements[1..];
+        for triple in edges.chunks_exact(3) {
+            let (u, v, w) = (triple[0] as u32, triple[1] as u32, triple[2]);
+            if (u == from && v == to) || (u == to && v == from) {
+                return Some(w);
+            }
+        }
+        None
+    }
+}
+
+impl ProofOfUsefulWork for HamiltonianPow {
+    fn problem_type() -> ProblemType {
+        ProblemType::HamiltonianPath
+    }
+
+    fn generate(tier: HardwareTier, difficulty_params: (usize, i64), seed: u64, timestamp: i64) -> Problem {
+        let (node_count, value_range) = difficulty_params;
+        let node_count = node_count.max(2);
+
+        // A Hamiltonian path along the fixed visiting order 0, 1, ...,
+        // node_count - 1, so the instance is guaranteed solvable; edges are
+        // added along that path only (a sparse graph, not a clique).
+        let order: Vec<u32> = (0..node_count as u32).collect();
+
+        let mut elements = vec![node_count as i64];
+        let mut total_cost: i64 = 0;
+        for (i, window) in order.windows(2).enumerate() {
+            let (u, v) = (window[0], window[1]);
+            let weight = pseudorandom_i64(seed, i as u64, value_range) + 1;
+            elements.push(u as i64);
+            elements.push(v as i64);
+            elements.push(weight);
+            total_cost = total_cost.saturating_add(weight);
+        }
+
+        Problem {
+            problem_type: ProblemType::HamiltonianPath,
+            tier,
+            elements,
+            target: total_cost,
+            timestamp,
+        }
+    }
+
+    fn verify(problem: &Problem, solution: &Solution, budget: &VerifyBudget) -> Result<VerifyResult, String> {
+        let mut tracker = BudgetTracker::new(budget);
+
+        let Some(node_count) = Self::node_count(problem) else {
+            return Ok(VerifyResult { valid: false, ops_used: 0 });
+        };
+
+        if solution.indices.len() != node_count as usize {
+            return Ok(VerifyResult { valid: false, ops_used: 0 });
+        }
+
+        let mut seen = HashSet::with_capacity(solution.indices.len());
+        for &node in &solution.indices {
+            tracker.charge(1, 8)?;
+            if node >= node_count || !seen.insert(node) {
+                return Ok(VerifyResult { valid: false, ops_used: tracker.ops_used });
+            }
+        }
+
+        let mut total_cost: i64 = 0;
+        for window in solution.indices.windows(2) {
+            tracker.charge(1, 8)?;
+            match Self::edge_weight(problem, window[0], window[1]) {
+                Some(weight) => {
+                    total_cost = match total_cost.checked_add(weight) {
+                        Some(c) => c,
+                        None => return Ok(VerifyResult { valid: false, ops_used: tracker.ops_used }),
+                    };
+                }
+                None => return Ok(VerifyResult { valid: false, ops_used: tracker.ops_used }),
+            }
+        }
+
+        Ok(VerifyResult {
+            valid: total_cost <= problem.target,
+            ops_used: tracker.ops_used,
+        })
+    }
+
+    fn canonical_encode(problem: &Problem) -> Vec<u8> {
+        encode_elements_and_target(problem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::VerifyBudget;
+
+    fn budget() -> VerifyBudget {
+        VerifyBudget::from_tier(HardwareTier::Desktop)
+    }
+
+    #[test]
+    fn test_subset_sum_generate_then_verify_is_solvable() {
+        let problem = SubsetSumPow::generate(HardwareTier::Desktop, (8, 100), 42, 0);
+        let half = problem.elements.len() / 2;
+        let solution = Solution {
+            indices: (0..half as u32).collect(),
+            timestamp: 0,
+        };
+        let result = SubsetSumPow::verify(&problem, &solution, &budget()).unwrap();
+        assert!(result.valid);
+    }
+
+    #[test]
+    fn test_knapsack_generate_then_verify_is_solvable() {
+        let problem = KnapsackPow::generate(HardwareTier::Desktop, (8, 100), 7, 0);
+        let half = (problem.elements.len() - 1) / 2 / 2;
+        let solution = Solution {
+            indices: (0..half as u32).collect(),
+            timestamp: 0,
+        };
+        let result = KnapsackPow::verify(&problem, &solution, &budget()).unwrap();
+        assert!(result.valid);
+    }
+
+    #[test]
+    fn test_knapsack_rejects_over_capacity() {
+        let problem = Problem {
+            problem_type: ProblemType::Knapsack,
+            tier: HardwareTier::Desktop,
+            elements: vec![5, /* cap */ 10, 1, 10, 1],
+            target: 1,
+            timestamp: 0,
+        };
+        let solution = Solution {
+            indices: vec![0, 1],
+            timestamp: 0,
+        };
+        let result = KnapsackPow::verify(&problem, &solution, &budget()).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_hamiltonian_generate_then_verify_is_solvable() {
+        let problem = HamiltonianPow::generate(HardwareTier::Desktop, (6, 50), 3, 0);
+        let solution = Solution {
+            indices: (0..6u32).collect(),
+            timestamp: 0,
+        };
+        let result = HamiltonianPow::verify(&problem, &solution, &budget()).unwrap();
+        assert!(result.valid);
+    }
+
+    #[test]
+    fn test_hamiltonian_rejects_repeated_node() {
+        let problem = HamiltonianPow::generate(HardwareTier::Desktop, (4, 50), 1, 0);
+        let solution = Solution {
+            indices: vec![0, 0, 1, 2],
+            timestamp: 0,
+        };
+        let result = HamiltonianPow::verify(&problem, &solution, &budget()).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_hamiltonian_rejects_missing_edge() {
+        let problem = HamiltonianPow::generate(HardwareTier::Desktop, (5, 50), 9, 0);
+        // `generate` only wires up edges along its own visiting order, so
+        // any permutation other than that order or its reverse is missing
+        // at least one edge.
+        let solution = Solution {
+            indices: vec![0, 2, 1, 3, 4],
+            timestamp: 0,
+        };
+        let result = HamiltonianPow::verify(&problem, &solution, &budget()).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_discriminant_is_stable() {
+        assert_eq!(ProblemType::SubsetSum.discriminant(), 0);
+        assert_eq!(ProblemType::Knapsack.discriminant(), 1);
+        assert_eq!(ProblemType::HamiltonianPath.discriminant(), 2);
+    }
+
+    #[test]
+    fn test_canonical_encode_begins_with_discriminant() {
+        let problem = SubsetSumPow::generate(HardwareTier::Desktop, (8, 100), 1, 0);
+        let encoded = SubsetSumPow::canonical_encode(&problem);
+        assert_eq!(encoded[0], ProblemType::SubsetSum.discriminant());
+    }
+
+    #[test]
+    fn test_verify_aborts_over_op_budget() {
+        let problem = KnapsackPow::generate(HardwareTier::Desktop, (64, 100), 11, 0);
+        let solution = Solution {
+            indices: (0..64u32).collect(),
+            timestamp: 0,
+        };
+        let tiny_budget = VerifyBudget {
+            max_ops: 1,
+            max_duration_ms: 1000,
+            max_memory_bytes: 1_000_000,
+        };
+        assert!(KnapsackPow::verify(&problem, &solution, &tiny_budget).is_err());
+    }
+}
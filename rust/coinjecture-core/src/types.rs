@@ -0,0 +1,146 @@
+//! Core consensus types shared across hashing, codec, and verification.
+
+use crate::hashes::{BlockHash, Commitment, MerkleRoot, MinerAddress};
+use serde::{Deserialize, Serialize};
+
+/// Current block header codec version. Headers with any other value are
+/// rejected by strict decoding.
+pub const CODEC_VERSION: u8 = 1;
+
+/// Hardware classes a SubsetSum problem can be generated for, used to scale
+/// problem size and verification budget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HardwareTier {
+    Mobile,
+    Desktop,
+    Workstation,
+    Server,
+    Cluster,
+}
+
+impl HardwareTier {
+    /// Inclusive (min, max) element count a problem may carry at this tier.
+    pub fn element_range(&self) -> (usize, usize) {
+        match self {
+            HardwareTier::Mobile => (8, 16),
+            HardwareTier::Desktop => (16, 32),
+            HardwareTier::Workstation => (32, 64),
+            HardwareTier::Server => (64, 128),
+            HardwareTier::Cluster => (128, 256),
+        }
+    }
+
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(HardwareTier::Mobile),
+            1 => Some(HardwareTier::Desktop),
+            2 => Some(HardwareTier::Workstation),
+            3 => Some(HardwareTier::Server),
+            4 => Some(HardwareTier::Cluster),
+            _ => None,
+        }
+    }
+}
+
+/// Family of proof-of-useful-work problem a block commits to. Registered in
+/// `problem::ProofOfUsefulWork` implementations; see that module for how
+/// each family generates instances and verifies solutions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProblemType {
+    SubsetSum,
+    Knapsack,
+    HamiltonianPath,
+}
+
+impl ProblemType {
+    /// Stable one-byte discriminant, independent of enum declaration order,
+    /// that feeds into the block commitment alongside the problem payload -
+    /// so consensus agrees on which problem family a block used even if
+    /// this enum later gains variants.
+    pub fn discriminant(&self) -> u8 {
+        match self {
+            ProblemType::SubsetSum => 0,
+            ProblemType::Knapsack => 1,
+            ProblemType::HamiltonianPath => 2,
+        }
+    }
+}
+
+/// A mining problem instance.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Problem {
+    pub problem_type: ProblemType,
+    pub tier: HardwareTier,
+    pub elements: Vec<i64>,
+    pub target: i64,
+    pub timestamp: i64,
+}
+
+/// A candidate solution. For SubsetSum, the indices into `Problem::elements`
+/// that sum to `Problem::target`; other families read `indices` as described
+/// in the `problem` module.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Solution {
+    pub indices: Vec<u32>,
+    pub timestamp: i64,
+}
+
+/// Resource limits a verifier must respect while checking a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyBudget {
+    pub max_ops: u64,
+    pub max_duration_ms: u64,
+    pub max_memory_bytes: u64,
+}
+
+impl VerifyBudget {
+    /// Scale the verification budget to a hardware tier; higher tiers get
+    /// larger budgets since they also generate larger problems.
+    pub fn from_tier(tier: HardwareTier) -> Self {
+        let (_, max_elem) = tier.element_range();
+        VerifyBudget {
+            max_ops: (max_elem as u64) * 1_000,
+            max_duration_ms: 100,
+            max_memory_bytes: 1024 * 1024,
+        }
+    }
+}
+
+/// Result of verifying a solution against a problem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifyResult {
+    pub valid: bool,
+    pub ops_used: u64,
+}
+
+/// A block header, the unit that gets hashed for proof-of-work.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockHeader {
+    pub codec_version: u8,
+    pub block_index: u64,
+    pub timestamp: i64,
+    pub parent_hash: BlockHash,
+    pub merkle_root: MerkleRoot,
+    pub miner_address: MinerAddress,
+    pub commitment: Commitment,
+    pub difficulty_target: u64,
+    pub nonce: u64,
+    pub extra_data: Vec<u8>,
+}
+
+impl Default for BlockHeader {
+    fn default() -> Self {
+        BlockHeader {
+            codec_version: CODEC_VERSION,
+            block_index: 0,
+            timestamp: 0,
+            parent_hash: BlockHash::from_bytes([0u8; 32]),
+            merkle_root: MerkleRoot::from_bytes([0u8; 32]),
+            miner_address: MinerAddress::from_bytes([0u8; 32]),
+            commitment: Commitment::from_bytes([0u8; 32]),
+            difficulty_target: 1,
+            nonce: 0,
+            extra_data: Vec::new(),
+        }
+    }
+}
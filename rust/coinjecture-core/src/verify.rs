@@ -0,0 +1,140 @@
+//! SubsetSum solution verification, budget-bounded so a malformed or
+//! adversarial solution can never make the verifier do unbounded work.
+
+use crate::types::{Problem, Solution, VerifyBudget, VerifyResult};
+use std::collections::HashSet;
+
+/// Verify that `solution.indices` selects elements from `problem.elements`
+/// summing to `problem.target`, never spending more than `budget.max_ops`.
+pub fn verify_solution(
+    problem: &Problem,
+    solution: &Solution,
+    budget: &VerifyBudget,
+) -> Result<VerifyResult, String> {
+    let mut ops_used: u64 = 0;
+
+    if solution.indices.is_empty() {
+        return Ok(VerifyResult {
+            valid: false,
+            ops_used,
+        });
+    }
+
+    // Reject duplicate indices - each element may only be used once.
+    let mut seen = HashSet::with_capacity(solution.indices.len());
+    for &index in &solution.indices {
+        ops_used = ops_used.saturating_add(1);
+        if ops_used > budget.max_ops {
+            return Err(format!(
+                "verification exceeded budget: {} > {}",
+                ops_used, budget.max_ops
+            ));
+        }
+        if !seen.insert(index) {
+            return Ok(VerifyResult {
+                valid: false,
+                ops_used,
+            });
+        }
+    }
+
+    let mut sum: i64 = 0;
+    for &index in &solution.indices {
+        ops_used = ops_used.saturating_add(1);
+        if ops_used > budget.max_ops {
+            return Err(format!(
+                "verification exceeded budget: {} > {}",
+                ops_used, budget.max_ops
+            ));
+        }
+
+        let element = match problem.elements.get(index as usize) {
+            Some(e) => *e,
+            None => {
+                return Ok(VerifyResult {
+                    valid: false,
+                    ops_used,
+                })
+            }
+        };
+
+        sum = match sum.checked_add(element) {
+            Some(s) => s,
+            None => {
+                return Ok(VerifyResult {
+                    valid: false,
+                    ops_used,
+                })
+            }
+        };
+    }
+
+    Ok(VerifyResult {
+        valid: sum == problem.target,
+        ops_used,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{HardwareTier, ProblemType};
+
+    fn budget() -> VerifyBudget {
+        VerifyBudget::from_tier(HardwareTier::Desktop)
+    }
+
+    #[test]
+    fn test_valid_solution() {
+        let problem = Problem {
+            problem_type: ProblemType::SubsetSum,
+            tier: HardwareTier::Desktop,
+            elements: vec![1, 2, 3, 4],
+            target: 5,
+            timestamp: 0,
+        };
+        let solution = Solution {
+            indices: vec![1, 2],
+            timestamp: 0,
+        };
+
+        let result = verify_solution(&problem, &solution, &budget()).unwrap();
+        assert!(result.valid);
+    }
+
+    #[test]
+    fn test_duplicate_indices_rejected() {
+        let problem = Problem {
+            problem_type: ProblemType::SubsetSum,
+            tier: HardwareTier::Desktop,
+            elements: vec![1, 2, 3],
+            target: 2,
+            timestamp: 0,
+        };
+        let solution = Solution {
+            indices: vec![0, 0],
+            timestamp: 0,
+        };
+
+        let result = verify_solution(&problem, &solution, &budget()).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_out_of_bounds_index_rejected() {
+        let problem = Problem {
+            problem_type: ProblemType::SubsetSum,
+            tier: HardwareTier::Desktop,
+            elements: vec![1, 2, 3],
+            target: 5,
+            timestamp: 0,
+        };
+        let solution = Solution {
+            indices: vec![99],
+            timestamp: 0,
+        };
+
+        let result = verify_solution(&problem, &solution, &budget()).unwrap();
+        assert!(!result.valid);
+    }
+}
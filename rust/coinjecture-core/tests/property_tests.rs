@@ -8,8 +8,10 @@
 //! - Hash determinism
 //! - Merkle tree properties
 //! - Commitment binding
+//! - Pluggable problem family generate/verify roundtrips and budget honoring
 
 use coinjecture_core::*;
+use coinjecture_core::problem::{HamiltonianPow, KnapsackPow, ProofOfUsefulWork, SubsetSumPow};
 use quickcheck::{Arbitrary, Gen, QuickCheck, TestResult};
 use quickcheck_macros::quickcheck;
 
@@ -30,8 +32,12 @@ impl Arbitrary for HardwareTier {
 
 impl Arbitrary for ProblemType {
     fn arbitrary(g: &mut Gen) -> Self {
-        // Only use production-ready types
-        ProblemType::SubsetSum
+        let types = [
+            ProblemType::SubsetSum,
+            ProblemType::Knapsack,
+            ProblemType::HamiltonianPath,
+        ];
+        *g.choose(&types).unwrap()
     }
 }
 
@@ -46,28 +52,28 @@ impl Arbitrary for BlockHeader {
                 for byte in &mut hash {
                     *byte = u8::arbitrary(g);
                 }
-                hash
+                hash.into()
             },
             merkle_root: {
                 let mut hash = [0u8; 32];
                 for byte in &mut hash {
                     *byte = u8::arbitrary(g);
                 }
-                hash
+                hash.into()
             },
             miner_address: {
                 let mut addr = [0u8; 32];
                 for byte in &mut addr {
                     *byte = u8::arbitrary(g);
                 }
-                addr
+                addr.into()
             },
             commitment: {
                 let mut comm = [0u8; 32];
                 for byte in &mut comm {
                     *byte = u8::arbitrary(g);
                 }
-                comm
+                comm.into()
             },
             difficulty_target: u64::arbitrary(g) % (2u64.pow(32)),
             nonce: u64::arbitrary(g),
@@ -135,7 +141,7 @@ fn prop_header_hash_determinism(header: BlockHeader) -> bool {
 fn prop_header_hash_length(header: BlockHeader) -> bool {
     // PROPERTY: All hashes are 32 bytes
     match codec::compute_header_hash(&header) {
-        Ok(hash) => hash.len() == 32,
+        Ok(hash) => hash.as_bytes().len() == 32,
         Err(_) => false,
     }
 }
@@ -163,14 +169,14 @@ fn prop_header_different_hash_on_mutation(header: BlockHeader) -> TestResult {
 fn prop_merkle_empty_root(x: u8) -> bool {
     // PROPERTY: Empty merkle tree has all-zeros root
     let root = merkle::compute_merkle_root(&[]);
-    root == [0u8; 32]
+    root == MerkleRoot([0u8; 32])
 }
 
 #[quickcheck]
 fn prop_merkle_single_leaf(hash: [u8; 32]) -> bool {
     // PROPERTY: Merkle tree of single leaf returns that leaf
     let root = merkle::compute_merkle_root(&[hash]);
-    root == hash
+    root == MerkleRoot(hash)
 }
 
 #[quickcheck]
@@ -207,6 +213,63 @@ fn prop_merkle_different_on_reorder(hashes: Vec<[u8; 32]>) -> TestResult {
     TestResult::from_bool(root1 != root2)
 }
 
+#[quickcheck]
+fn prop_merkle_proof_verifies_generated_index(hashes: Vec<[u8; 32]>, index_seed: usize) -> TestResult {
+    if hashes.is_empty() || hashes.len() > 200 {
+        return TestResult::discard();
+    }
+
+    // PROPERTY: a proof generated for any valid index verifies against the
+    // root produced by compute_merkle_root for that same leaf set.
+    let index = index_seed % hashes.len();
+    let root = merkle::compute_merkle_root(&hashes);
+    let proof = merkle::compute_merkle_proof(&hashes, index).expect("index in range");
+
+    TestResult::from_bool(merkle::verify_merkle_proof(&root, &hashes[index], &proof))
+}
+
+#[quickcheck]
+fn prop_merkle_proof_rejects_mutated_leaf(
+    hashes: Vec<[u8; 32]>,
+    index_seed: usize,
+    mutated_leaf: [u8; 32],
+) -> TestResult {
+    if hashes.is_empty() || hashes.len() > 200 {
+        return TestResult::discard();
+    }
+
+    let index = index_seed % hashes.len();
+    if mutated_leaf == hashes[index] {
+        return TestResult::discard();
+    }
+
+    let root = merkle::compute_merkle_root(&hashes);
+    let proof = merkle::compute_merkle_proof(&hashes, index).expect("index in range");
+
+    TestResult::from_bool(!merkle::verify_merkle_proof(&root, &mutated_leaf, &proof))
+}
+
+#[quickcheck]
+fn prop_filter_never_false_negative(header: BlockHeader, tx_hashes: Vec<[u8; 32]>) -> TestResult {
+    if tx_hashes.len() > 200 {
+        return TestResult::discard();
+    }
+
+    // PROPERTY: every element a filter was built over must test positive
+    // against that same filter - a false negative would silently hide a
+    // transaction from a light client.
+    let block_hash = codec::compute_header_hash(&header).unwrap();
+    let filter = filter::build_block_filter(&header, &tx_hashes).unwrap();
+
+    let all_present = tx_hashes
+        .iter()
+        .all(|tx_hash| filter::filter_may_contain(&filter, &block_hash, tx_hash))
+        && filter::filter_may_contain(&filter, &block_hash, header.commitment.as_ref())
+        && filter::filter_may_contain(&filter, &block_hash, header.miner_address.as_ref());
+
+    TestResult::from_bool(all_present)
+}
+
 #[quickcheck]
 fn prop_commitment_epoch_binding(parent_hash: [u8; 32], block_index: u64) -> bool {
     // PROPERTY: Different epochs produce different epoch salts
@@ -250,6 +313,70 @@ fn prop_verify_budget_scaling(tier: HardwareTier) -> bool {
     budget.max_ops > 0 && budget.max_duration_ms > 0 && budget.max_memory_bytes > 0
 }
 
+#[quickcheck]
+fn prop_retarget_raises_difficulty_for_faster_blocks(
+    parent_target: u64,
+    parent_timestamp: i64,
+    target_interval_secs: i64,
+) -> TestResult {
+    if parent_target < 2048 || target_interval_secs < 2 {
+        return TestResult::discard();
+    }
+    let Some(block_timestamp) = parent_timestamp.checked_add(1) else {
+        return TestResult::discard();
+    };
+
+    // PROPERTY: a block mined faster than the target interval never
+    // lowers difficulty.
+    let new_target = difficulty::retarget(
+        parent_target,
+        parent_timestamp,
+        block_timestamp,
+        target_interval_secs,
+    );
+
+    TestResult::from_bool(new_target >= parent_target)
+}
+
+#[quickcheck]
+fn prop_retarget_lowers_difficulty_for_slower_blocks(
+    parent_target: u64,
+    parent_timestamp: i64,
+    target_interval_secs: i64,
+) -> TestResult {
+    if parent_target < 2048 || target_interval_secs < 1 {
+        return TestResult::discard();
+    }
+    let Some(block_timestamp) = target_interval_secs
+        .checked_mul(100)
+        .and_then(|span| parent_timestamp.checked_add(span))
+    else {
+        return TestResult::discard();
+    };
+
+    // PROPERTY: a block mined much slower than the target interval never
+    // raises difficulty.
+    let new_target = difficulty::retarget(
+        parent_target,
+        parent_timestamp,
+        block_timestamp,
+        target_interval_secs,
+    );
+
+    TestResult::from_bool(new_target <= parent_target)
+}
+
+#[quickcheck]
+fn prop_problem_params_respect_tier_constraints(target: u64, tier: HardwareTier) -> bool {
+    // PROPERTY: no matter how difficulty has drifted, the element count it
+    // maps to never escapes the bounds `prop_tier_constraints_enforced`
+    // checks for actual generated problems.
+    let (min_elem, max_elem) = tier.element_range();
+    let (elem_count, value_range) = difficulty::problem_params_for_target(target, tier);
+
+    elem_count >= min_elem && elem_count <= max_elem && value_range >= 1
+}
+
 #[quickcheck]
 fn prop_hash_sha256_output_size(data: Vec<u8>) -> bool {
     // PROPERTY: SHA-256 always produces 32 bytes
@@ -275,14 +402,130 @@ fn prop_hash_different_inputs_different_outputs(data1: Vec<u8>, data2: Vec<u8>)
 
 #[quickcheck]
 fn prop_header_encode_decode_roundtrip(header: BlockHeader) -> TestResult {
-    // PROPERTY: decode(encode(x)) == x
+    // PROPERTY: decode_block_header(encode_block_header(x)) == x
     let encoded = match codec::encode_block_header(&header) {
         Ok(bytes) => bytes,
         Err(_) => return TestResult::failed(),
     };
 
-    // Decoding not yet fully implemented, so just verify encoding succeeds
-    TestResult::from_bool(!encoded.is_empty())
+    match codec::decode_block_header(&encoded) {
+        Ok(decoded) => TestResult::from_bool(decoded == header),
+        Err(_) => TestResult::failed(),
+    }
+}
+
+#[quickcheck]
+fn prop_codec_equivalence_msgpack_json(header: BlockHeader) -> TestResult {
+    // PROPERTY: msgpack and JSON encodings of the same header decode back
+    // to identical headers - the "Codec equivalence" claim the golden
+    // suite relies on, enforced here for arbitrary headers rather than
+    // just the frozen fixtures.
+    let msgpack_bytes = match codec::encode_msgpack(&header) {
+        Ok(bytes) => bytes,
+        Err(_) => return TestResult::failed(),
+    };
+    let json = match serde_json::to_string(&header) {
+        Ok(json) => json,
+        Err(_) => return TestResult::failed(),
+    };
+
+    let from_msgpack: BlockHeader = match codec::decode_block_header(&msgpack_bytes) {
+        Ok(header) => header,
+        Err(_) => return TestResult::failed(),
+    };
+    let from_json: BlockHeader = match codec::decode_json(&json) {
+        Ok(header) => header,
+        Err(_) => return TestResult::failed(),
+    };
+
+    TestResult::from_bool(from_msgpack == header && from_json == header)
+}
+
+// ==================== PLUGGABLE PROBLEM FAMILY PROPERTIES ====================
+
+/// `(element_count, value_range)` difficulty params for `tier`, sized off
+/// its minimum element count so every family's property tests stay fast.
+fn small_difficulty_params(tier: HardwareTier) -> (usize, i64) {
+    let (min_elem, _) = tier.element_range();
+    (min_elem.max(2), 1000)
+}
+
+#[quickcheck]
+fn prop_subset_sum_generate_is_solvable(seed: u64, tier: HardwareTier) -> bool {
+    let problem = SubsetSumPow::generate(tier, small_difficulty_params(tier), seed, 0);
+    let half = problem.elements.len() / 2;
+    let solution = Solution {
+        indices: (0..half as u32).collect(),
+        timestamp: 0,
+    };
+
+    match SubsetSumPow::verify(&problem, &solution, &VerifyBudget::from_tier(tier)) {
+        Ok(result) => result.valid,
+        Err(_) => false,
+    }
+}
+
+#[quickcheck]
+fn prop_knapsack_generate_is_solvable(seed: u64, tier: HardwareTier) -> bool {
+    let problem = KnapsackPow::generate(tier, small_difficulty_params(tier), seed, 0);
+    let item_count = (problem.elements.len() - 1) / 2;
+    let solution = Solution {
+        indices: (0..(item_count / 2) as u32).collect(),
+        timestamp: 0,
+    };
+
+    match KnapsackPow::verify(&problem, &solution, &VerifyBudget::from_tier(tier)) {
+        Ok(result) => result.valid,
+        Err(_) => false,
+    }
+}
+
+#[quickcheck]
+fn prop_hamiltonian_generate_is_solvable(seed: u64, tier: HardwareTier) -> bool {
+    let problem = HamiltonianPow::generate(tier, small_difficulty_params(tier), seed, 0);
+    let node_count = problem.elements[0] as u32;
+    let solution = Solution {
+        indices: (0..node_count).collect(),
+        timestamp: 0,
+    };
+
+    match HamiltonianPow::verify(&problem, &solution, &VerifyBudget::from_tier(tier)) {
+        Ok(result) => result.valid,
+        Err(_) => false,
+    }
+}
+
+#[quickcheck]
+fn prop_problem_family_verify_honors_op_budget(seed: u64, tier: HardwareTier) -> bool {
+    // PROPERTY: every family's verify aborts rather than overruns a budget
+    // too small to check even its first element.
+    let starved = VerifyBudget {
+        max_ops: 0,
+        max_duration_ms: 1000,
+        max_memory_bytes: 1_000_000,
+    };
+
+    let subset_sum = SubsetSumPow::generate(tier, small_difficulty_params(tier), seed, 0);
+    let subset_sum_solution = Solution {
+        indices: vec![0],
+        timestamp: 0,
+    };
+
+    let knapsack = KnapsackPow::generate(tier, small_difficulty_params(tier), seed, 0);
+    let knapsack_solution = Solution {
+        indices: vec![0],
+        timestamp: 0,
+    };
+
+    let hamiltonian = HamiltonianPow::generate(tier, small_difficulty_params(tier), seed, 0);
+    let hamiltonian_solution = Solution {
+        indices: (0..hamiltonian.elements[0] as u32).collect(),
+        timestamp: 0,
+    };
+
+    SubsetSumPow::verify(&subset_sum, &subset_sum_solution, &starved).is_err()
+        && KnapsackPow::verify(&knapsack, &knapsack_solution, &starved).is_err()
+        && HamiltonianPow::verify(&hamiltonian, &hamiltonian_solution, &starved).is_err()
 }
 
 // ==================== PERFORMANCE PROPERTIES ====================
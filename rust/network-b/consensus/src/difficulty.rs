@@ -0,0 +1,133 @@
+// Difficulty abstraction and sliding-window retargeting
+//
+// Wraps the raw work difficulty in a u128 so retargeting math has headroom
+// well beyond any realistic SubsetSum work score, and keeps every operation
+// checked so a malicious or buggy timestamp sequence can't panic or wrap
+// the chain's difficulty to zero.
+
+use std::cmp::max;
+
+/// A block's difficulty. Never zero - `to_target` divides by it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Difficulty(u128);
+
+impl Difficulty {
+    pub const MIN: Difficulty = Difficulty(1);
+    pub const MAX: Difficulty = Difficulty(u128::MAX);
+
+    /// Construct a difficulty, clamping anything below `MIN` up to it.
+    pub fn new(value: u128) -> Self {
+        Difficulty(value.max(Self::MIN.0))
+    }
+
+    pub fn value(&self) -> u128 {
+        self.0
+    }
+
+    /// Add two difficulties, saturating to `MAX` on overflow.
+    pub fn checked_add(self, rhs: Difficulty) -> Difficulty {
+        match self.0.checked_add(rhs.0) {
+            Some(sum) => Difficulty(sum),
+            None => Difficulty::MAX,
+        }
+    }
+
+    /// Multiply two difficulties, saturating to `MAX` on overflow.
+    pub fn checked_mul(self, rhs: Difficulty) -> Difficulty {
+        match self.0.checked_mul(rhs.0) {
+            Some(product) => Difficulty(product),
+            None => Difficulty::MAX,
+        }
+    }
+
+    /// Convert to a PoW-style target: smaller difficulty means larger target.
+    pub fn to_target(&self, max_target: u128) -> u128 {
+        max_target / self.0
+    }
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Difficulty::MIN
+    }
+}
+
+/// Number of blocks in the retargeting sliding window.
+pub const RETARGET_WINDOW: usize = 120;
+
+/// Recompute difficulty from the timestamps of the last `RETARGET_WINDOW`
+/// blocks (oldest first), damping oscillation by clamping the observed
+/// timespan to `[target_timespan/4, target_timespan*4]`.
+///
+/// `new_difficulty = old_difficulty * target_timespan / actual_timespan`,
+/// computed with the checked ops above so a pair of colliding timestamps
+/// can never divide by zero.
+pub fn retarget(old_difficulty: Difficulty, timestamps: &[i64], target_timespan: i64) -> Difficulty {
+    if timestamps.len() < 2 || target_timespan <= 0 {
+        return old_difficulty;
+    }
+
+    let oldest_ts = timestamps[0];
+    let newest_ts = timestamps[timestamps.len() - 1];
+    let actual_timespan = newest_ts.saturating_sub(oldest_ts).max(1);
+
+    let min_timespan = max(target_timespan / 4, 1);
+    let max_timespan = target_timespan.saturating_mul(4);
+    let clamped_timespan = actual_timespan.clamp(min_timespan, max_timespan);
+
+    let old = old_difficulty.value();
+    let scaled = old
+        .checked_mul(target_timespan as u128)
+        .map(|v| v / clamped_timespan as u128)
+        .unwrap_or(u128::MAX);
+
+    Difficulty::new(scaled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimum_clamp_rejects_zero() {
+        assert_eq!(Difficulty::new(0), Difficulty::MIN);
+    }
+
+    #[test]
+    fn test_checked_add_saturates() {
+        assert_eq!(Difficulty::MAX.checked_add(Difficulty::new(1)), Difficulty::MAX);
+    }
+
+    #[test]
+    fn test_checked_mul_saturates() {
+        let d = Difficulty::new(u128::MAX / 2).checked_mul(Difficulty::new(3));
+        assert_eq!(d, Difficulty::MAX);
+    }
+
+    #[test]
+    fn test_to_target_inverse_of_difficulty() {
+        let d = Difficulty::new(1000);
+        assert_eq!(d.to_target(1_000_000), 1000);
+    }
+
+    #[test]
+    fn test_retarget_speeds_up_when_blocks_come_fast() {
+        let old = Difficulty::new(1000);
+        let new = retarget(old, &[0, 60], 120);
+        assert!(new.value() > old.value());
+    }
+
+    #[test]
+    fn test_retarget_slows_down_when_blocks_come_slow() {
+        let old = Difficulty::new(1000);
+        let new = retarget(old, &[0, 480], 120);
+        assert!(new.value() < old.value());
+    }
+
+    #[test]
+    fn test_retarget_never_divides_by_zero_on_colliding_timestamps() {
+        let old = Difficulty::new(1000);
+        let new = retarget(old, &[100, 100], 120);
+        assert!(new.value() > 0);
+    }
+}
@@ -1,8 +1,10 @@
 // COINjecture Consensus Engine
 // Work score calculation and difficulty adjustment
 
+pub mod difficulty;
 pub mod work_score;
 pub mod miner;
 
+pub use difficulty::*;
 pub use work_score::*;
 pub use miner::*;
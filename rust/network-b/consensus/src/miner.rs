@@ -0,0 +1,268 @@
+// Mempool and block-template assembler.
+//
+// Selects pending transactions by descending fee-per-byte until a
+// configurable block size limit is hit, then builds a `BlockHeader`
+// template (merkle root included) ready for mining.
+
+use coinject_core::Balance;
+use coinjecture_core::hash::sha256;
+use coinjecture_core::merkle::compute_merkle_root;
+use coinjecture_core::types::{BlockHeader, CODEC_VERSION};
+use std::collections::HashMap;
+
+/// A pending transaction. This is the minimal shape needed for fee-based
+/// ordering and merkle inclusion; signature/script fields live upstream in
+/// the state crate's transaction types.
+#[derive(Clone, Debug)]
+pub struct Transaction {
+    pub hash: [u8; 32],
+    pub fee: Balance,
+    pub size_bytes: usize,
+    pub serialized: Vec<u8>,
+}
+
+impl Transaction {
+    pub fn fee_per_byte(&self) -> f64 {
+        if self.size_bytes == 0 {
+            return 0.0;
+        }
+        self.fee as f64 / self.size_bytes as f64
+    }
+}
+
+/// A transaction plus its cached hash, so dedup in the mempool is an O(1)
+/// hash-set lookup rather than a full transaction comparison.
+#[derive(Clone, Debug)]
+pub struct IndexedTransaction {
+    pub tx: Transaction,
+    pub hash: [u8; 32],
+}
+
+impl IndexedTransaction {
+    pub fn new(tx: Transaction) -> Self {
+        let hash = tx.hash;
+        IndexedTransaction { tx, hash }
+    }
+}
+
+impl PartialEq for IndexedTransaction {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+    }
+}
+impl Eq for IndexedTransaction {}
+
+/// Pending-transaction pool, bounded by a memory cap and ordered for greedy
+/// block assembly by descending fee-per-byte.
+pub struct Mempool {
+    transactions: HashMap<[u8; 32], IndexedTransaction>,
+    max_memory_bytes: usize,
+    used_bytes: usize,
+}
+
+impl Mempool {
+    pub fn new(max_memory_bytes: usize) -> Self {
+        Mempool {
+            transactions: HashMap::new(),
+            max_memory_bytes,
+            used_bytes: 0,
+        }
+    }
+
+    /// Insert a transaction, deduping by hash, then evict the
+    /// lowest-fee-per-byte entries until the pool is back under its memory cap.
+    pub fn insert(&mut self, tx: Transaction) {
+        let indexed = IndexedTransaction::new(tx);
+        if self.transactions.contains_key(&indexed.hash) {
+            return;
+        }
+
+        self.used_bytes += indexed.tx.size_bytes;
+        self.transactions.insert(indexed.hash, indexed);
+        self.evict_to_cap();
+    }
+
+    fn evict_to_cap(&mut self) {
+        while self.used_bytes > self.max_memory_bytes && !self.transactions.is_empty() {
+            let lowest_hash = self
+                .transactions
+                .values()
+                .min_by(|a, b| a.tx.fee_per_byte().partial_cmp(&b.tx.fee_per_byte()).unwrap())
+                .map(|indexed| indexed.hash)
+                .expect("pool is non-empty");
+
+            if let Some(removed) = self.transactions.remove(&lowest_hash) {
+                self.used_bytes -= removed.tx.size_bytes;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Greedily select transactions by descending fee-per-byte, keeping the
+    /// total within `max_block_size` (bytes): a transaction that does not
+    /// fit is skipped and later, smaller ones are still considered.
+    pub fn select_for_block(&self, max_block_size: usize) -> Vec<Transaction> {
+        let mut candidates: Vec<&IndexedTransaction> = self.transactions.values().collect();
+        candidates.sort_by(|a, b| {
+            b.tx
+                .fee_per_byte()
+                .partial_cmp(&a.tx.fee_per_byte())
+                .unwrap()
+        });
+
+        let mut selected = Vec::new();
+        let mut total_size = 0usize;
+        for candidate in candidates {
+            if total_size + candidate.tx.size_bytes > max_block_size {
+                continue;
+            }
+            total_size += candidate.tx.size_bytes;
+            selected.push(candidate.tx.clone());
+        }
+        selected
+    }
+}
+
+/// Build the sentinel coinbase/reward transaction, mirroring a null-outpoint
+/// input: it spends nothing and mints `reward` to `miner_address`. `reward`
+/// is produced upstream by `RewardCalculator::calculate_reward`; it's passed
+/// in here rather than the miner module depending on the tokenomics crate.
+pub fn make_coinbase_transaction(reward: Balance, miner_address: [u8; 32]) -> Transaction {
+    const NULL_OUTPOINT: [u8; 32] = [0u8; 32];
+
+    let mut serialized = Vec::with_capacity(72);
+    serialized.extend_from_slice(&NULL_OUTPOINT);
+    serialized.extend_from_slice(&(reward as u64).to_le_bytes());
+    serialized.extend_from_slice(&miner_address);
+
+    Transaction {
+        hash: sha256(&serialized),
+        fee: 0,
+        size_bytes: serialized.len(),
+        serialized,
+    }
+}
+
+/// Assembles a mined block template: selects mempool transactions, prepends
+/// the coinbase, and populates a `BlockHeader` whose `merkle_root` commits
+/// to the resulting transaction set.
+pub struct BlockAssembler {
+    pub max_block_size: usize,
+}
+
+impl BlockAssembler {
+    pub fn new(max_block_size: usize) -> Self {
+        BlockAssembler { max_block_size }
+    }
+
+    pub fn assemble(
+        &self,
+        mempool: &Mempool,
+        coinbase: Transaction,
+        parent_hash: [u8; 32],
+        block_index: u64,
+        timestamp: i64,
+        difficulty_target: u64,
+        miner_address: [u8; 32],
+    ) -> (BlockHeader, Vec<Transaction>) {
+        let budget = self.max_block_size.saturating_sub(coinbase.size_bytes);
+        let mut selected = vec![coinbase];
+        selected.extend(mempool.select_for_block(budget));
+
+        let tx_hashes: Vec<[u8; 32]> = selected.iter().map(|tx| tx.hash).collect();
+        let merkle_root = compute_merkle_root(&tx_hashes);
+
+        let header = BlockHeader {
+            codec_version: CODEC_VERSION,
+            block_index,
+            timestamp,
+            parent_hash: parent_hash.into(),
+            merkle_root,
+            miner_address: miner_address.into(),
+            commitment: [0u8; 32].into(),
+            difficulty_target,
+            nonce: 0,
+            extra_data: Vec::new(),
+        };
+
+        (header, selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(hash_byte: u8, fee: Balance, size_bytes: usize) -> Transaction {
+        Transaction {
+            hash: [hash_byte; 32],
+            fee,
+            size_bytes,
+            serialized: vec![0u8; size_bytes],
+        }
+    }
+
+    #[test]
+    fn test_insert_dedups_by_hash() {
+        let mut pool = Mempool::new(1024);
+        pool.insert(tx(1, 100, 10));
+        pool.insert(tx(1, 999, 10)); // same hash, should be ignored
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn test_select_for_block_orders_by_fee_per_byte() {
+        let mut pool = Mempool::new(1024);
+        pool.insert(tx(1, 10, 100)); // 0.1 fee/byte
+        pool.insert(tx(2, 100, 100)); // 1.0 fee/byte
+        pool.insert(tx(3, 50, 100)); // 0.5 fee/byte
+
+        let selected = pool.select_for_block(1024);
+        assert_eq!(selected[0].hash, [2u8; 32]);
+        assert_eq!(selected[1].hash, [3u8; 32]);
+        assert_eq!(selected[2].hash, [1u8; 32]);
+    }
+
+    #[test]
+    fn test_select_for_block_respects_size_limit() {
+        let mut pool = Mempool::new(1024);
+        pool.insert(tx(1, 100, 100));
+        pool.insert(tx(2, 100, 100));
+
+        let selected = pool.select_for_block(100);
+        assert_eq!(selected.len(), 1);
+    }
+
+    #[test]
+    fn test_eviction_drops_lowest_fee_per_byte_when_over_cap() {
+        let mut pool = Mempool::new(150);
+        pool.insert(tx(1, 10, 100)); // low fee/byte
+        pool.insert(tx(2, 100, 100)); // high fee/byte, pushes pool over cap
+
+        assert_eq!(pool.len(), 1);
+        assert!(pool.select_for_block(1024)[0].hash == [2u8; 32]);
+    }
+
+    #[test]
+    fn test_assemble_prepends_coinbase_and_builds_merkle_root() {
+        let mut pool = Mempool::new(1024);
+        pool.insert(tx(1, 10, 10));
+
+        let coinbase = make_coinbase_transaction(5_000_000, [9u8; 32]);
+        let assembler = BlockAssembler::new(1024);
+        let (header, transactions) =
+            assembler.assemble(&pool, coinbase.clone(), [0u8; 32], 1, 1000, 100, [9u8; 32]);
+
+        assert_eq!(transactions[0].hash, coinbase.hash);
+        let expected_root =
+            compute_merkle_root(&transactions.iter().map(|t| t.hash).collect::<Vec<_>>());
+        assert_eq!(header.merkle_root, expected_root);
+    }
+}
@@ -0,0 +1,75 @@
+// Sliding-window work score aggregation
+//
+// Tracks the timestamps and work scores of recently mined blocks so the
+// epoch average fed to reward calculation and the retargeted difficulty
+// are always derived from the same window.
+
+use crate::difficulty::{retarget, Difficulty, RETARGET_WINDOW};
+
+/// Tracks the last `RETARGET_WINDOW` blocks' timestamps and work scores.
+pub struct EpochWorkTracker {
+    timestamps: Vec<i64>,
+    work_scores: Vec<f64>,
+    difficulty: Difficulty,
+    target_timespan: i64,
+}
+
+impl EpochWorkTracker {
+    pub fn new(target_timespan: i64) -> Self {
+        EpochWorkTracker {
+            timestamps: Vec::new(),
+            work_scores: Vec::new(),
+            difficulty: Difficulty::default(),
+            target_timespan,
+        }
+    }
+
+    /// Record a newly mined block, retaining only the trailing window.
+    pub fn record_block(&mut self, timestamp: i64, work_score: f64) {
+        self.timestamps.push(timestamp);
+        self.work_scores.push(work_score);
+
+        if self.timestamps.len() > RETARGET_WINDOW {
+            self.timestamps.remove(0);
+            self.work_scores.remove(0);
+        }
+    }
+
+    /// Average work score across the current window.
+    pub fn average_work(&self) -> f64 {
+        if self.work_scores.is_empty() {
+            return 1.0;
+        }
+        self.work_scores.iter().sum::<f64>() / self.work_scores.len() as f64
+    }
+
+    /// Retarget difficulty from the timestamps observed so far.
+    pub fn retarget_difficulty(&mut self) -> Difficulty {
+        self.difficulty = retarget(self.difficulty, &self.timestamps, self.target_timespan);
+        self.difficulty
+    }
+
+    pub fn current_difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_work_defaults_to_one_when_empty() {
+        let tracker = EpochWorkTracker::new(120);
+        assert_eq!(tracker.average_work(), 1.0);
+    }
+
+    #[test]
+    fn test_window_evicts_oldest_entries() {
+        let mut tracker = EpochWorkTracker::new(120);
+        for i in 0..(RETARGET_WINDOW + 10) {
+            tracker.record_block(i as i64, 1.0);
+        }
+        assert_eq!(tracker.timestamps.len(), RETARGET_WINDOW);
+    }
+}
@@ -0,0 +1,83 @@
+//! Fuzz target for the EIP-1559-style fee market
+//!
+//! This tests that an arbitrary sequence of blocks, each with an arbitrary
+//! gas usage, run through `FeeMarket::update_base_fee` never causes:
+//! - Panics (especially integer overflow on a maliciously large base fee)
+//! - `base_fee` dropping below `min_base_fee`
+//! - `burn_amount + miner_reward != total_fee`
+//!
+//! Expected behavior: every invariant holds regardless of input.
+
+#![no_main]
+
+use libfuzzer_sys::{arbitrary::{Arbitrary, Unstructured}, fuzz_target};
+use mempool::{FeeMarket, FeeMarketConfig};
+
+#[derive(Debug)]
+struct FuzzInput {
+    config: FeeMarketConfig,
+    gas_used_sequence: Vec<u64>,
+    total_fee: u64,
+    priority_fee: u64,
+}
+
+impl<'a> Arbitrary<'a> for FuzzInput {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let target_gas = u.arbitrary().unwrap_or(15_000);
+        let max_gas = u.arbitrary().unwrap_or(30_000);
+        let initial_base_fee: u64 = u.arbitrary().unwrap_or(1000);
+        let max_change_denominator = u.int_in_range(1..=64)?;
+        let min_base_fee: u64 = u.arbitrary().unwrap_or(100);
+
+        let config = FeeMarketConfig {
+            target_transactions: u.int_in_range(1..=1000)?,
+            max_transactions: u.int_in_range(1..=2000)?,
+            target_gas,
+            max_gas,
+            initial_base_fee,
+            max_change_denominator,
+            min_base_fee,
+        };
+
+        let sequence_len = u.int_in_range(0..=64)?;
+        let gas_used_sequence = (0..sequence_len)
+            .map(|_| u.arbitrary().unwrap_or(0))
+            .collect();
+
+        Ok(FuzzInput {
+            config,
+            gas_used_sequence,
+            total_fee: u.arbitrary().unwrap_or(0),
+            priority_fee: u.arbitrary().unwrap_or(0),
+        })
+    }
+}
+
+fuzz_target!(|input: FuzzInput| {
+    let mut market = FeeMarket::new(input.config.clone());
+
+    for gas_used in &input.gas_used_sequence {
+        market.update_base_fee(*gas_used);
+
+        assert!(
+            market.base_fee >= input.config.min_base_fee,
+            "base_fee {} fell below min_base_fee {}",
+            market.base_fee,
+            input.config.min_base_fee
+        );
+    }
+
+    // calculate_total_fee / validate_fee must never panic on arbitrary
+    // priority fees, even ones that would overflow plain addition.
+    let _ = market.calculate_total_fee(input.priority_fee);
+    let _ = market.validate_fee(input.total_fee, input.priority_fee);
+
+    let miner_reward = market.get_miner_reward(input.total_fee, input.priority_fee);
+    let burn_amount = market.get_burn_amount(input.total_fee, input.priority_fee);
+
+    assert_eq!(
+        burn_amount.saturating_add(miner_reward),
+        input.total_fee,
+        "burn_amount + miner_reward must equal total_fee"
+    );
+});
@@ -0,0 +1,224 @@
+// Confirmation-depth fee estimator
+// Recommends a priority fee for a target confirmation depth from observed
+// confirmation history, analogous to Bitcoin Core's estimatefee/estimatesmartfee.
+
+use coinject_core::Balance;
+
+use crate::fee_market::FeeMarket;
+
+/// Exponentially-spaced tip histogram: `bucket_count` buckets starting at
+/// `min_bucket` and growing by `growth` each step, wide enough at the low
+/// end to separate dust tips and wide enough at the high end that a few
+/// outlier whales don't each get their own bucket. Bounds are rounded to
+/// whole units and raised to one above the previous bound wherever
+/// rounding would repeat it, so they are strictly ascending.
+#[derive(Clone, Debug)]
+pub struct FeeEstimatorConfig {
+    /// Lower bound of the smallest bucket.
+    pub min_bucket: Balance,
+    /// Multiplier applied to go from one bucket's lower bound to the next.
+    pub growth: f64,
+    /// Number of buckets in the histogram.
+    pub bucket_count: usize,
+    /// Longest confirmation depth tracked; `estimate_priority_fee` clamps
+    /// `target_blocks` to this.
+    pub max_target_blocks: usize,
+    /// Minimum observations a bucket needs before its success rate is
+    /// trusted at all.
+    pub min_samples: usize,
+}
+
+impl Default for FeeEstimatorConfig {
+    fn default() -> Self {
+        FeeEstimatorConfig {
+            min_bucket: 1,
+            growth: 1.5,
+            bucket_count: 30,
+            max_target_blocks: 25,
+            min_samples: 10,
+        }
+    }
+}
+
+/// Confirmations observed so far for one tip bucket: how many transactions
+/// landed within each depth `1..=max_target_blocks`, and how many were
+/// observed in total (the denominator for every depth's success rate).
+#[derive(Clone, Debug, Default)]
+struct BucketStats {
+    /// `confirmed_within[d - 1]` = count of transactions in this bucket
+    /// that confirmed within `d` blocks.
+    confirmed_within: Vec<u64>,
+    total: u64,
+}
+
+impl BucketStats {
+    fn new(max_target_blocks: usize) -> Self {
+        BucketStats {
+            confirmed_within: vec![0; max_target_blocks],
+            total: 0,
+        }
+    }
+
+    fn record(&mut self, blocks_to_confirm: usize, max_target_blocks: usize) {
+        self.total += 1;
+        for depth in blocks_to_confirm.max(1)..=max_target_blocks {
+            self.confirmed_within[depth - 1] += 1;
+        }
+    }
+
+    fn success_rate(&self, target_blocks: usize) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.confirmed_within[target_blocks - 1] as f64 / self.total as f64
+    }
+}
+
+/// Data-driven priority-fee recommendation built from confirmed blocks.
+#[derive(Clone, Debug)]
+pub struct FeeEstimator {
+    config: FeeEstimatorConfig,
+    /// Bucket lower bounds, strictly ascending, with `bucket_bounds[i]`
+    /// tracked by `stats[i]`.
+    bucket_bounds: Vec<Balance>,
+    stats: Vec<BucketStats>,
+}
+
+impl FeeEstimator {
+    pub fn new(config: FeeEstimatorConfig) -> Self {
+        let mut bucket_bounds: Vec<Balance> = Vec::with_capacity(config.bucket_count);
+        for i in 0..config.bucket_count {
+            let bound = config.min_bucket as f64 * config.growth.powi(i as i32);
+            let bound = bound.round().max(1.0) as Balance;
+            let floor = bucket_bounds.last().map_or(0, |prev| *prev + 1);
+            bucket_bounds.push(bound.max(floor));
+        }
+        let stats = (0..config.bucket_count)
+            .map(|_| BucketStats::new(config.max_target_blocks))
+            .collect();
+
+        FeeEstimator {
+            config,
+            bucket_bounds,
+            stats,
+        }
+    }
+
+    /// Record one confirmed transaction's paid priority fee and how many
+    /// blocks it waited before being mined.
+    pub fn record_confirmation(&mut self, priority_fee: Balance, blocks_to_confirm: usize) {
+        let bucket = self.bucket_for(priority_fee);
+        self.stats[bucket].record(blocks_to_confirm, self.config.max_target_blocks);
+    }
+
+    /// The highest bucket index whose lower bound is `<= priority_fee`.
+    fn bucket_for(&self, priority_fee: Balance) -> usize {
+        match self.bucket_bounds.binary_search(&priority_fee) {
+            Ok(i) => i,
+            Err(0) => 0,
+            Err(i) => i - 1,
+        }
+    }
+
+    /// The lowest tip that historically confirmed within `target_blocks`
+    /// blocks at least `confidence` (`0.0..=1.0`) of the time, falling back
+    /// to `market.base_fee` when no bucket with enough samples to trust
+    /// clears that threshold.
+    pub fn estimate_priority_fee(
+        &self,
+        market: &FeeMarket,
+        target_blocks: usize,
+        confidence: f64,
+    ) -> Balance {
+        let target_blocks = target_blocks.clamp(1, self.config.max_target_blocks);
+
+        for (bucket, bound) in self.bucket_bounds.iter().enumerate() {
+            let stats = &self.stats[bucket];
+            if stats.total < self.config.min_samples as u64 {
+                continue;
+            }
+            if stats.success_rate(target_blocks) >= confidence {
+                return *bound;
+            }
+        }
+
+        market.base_fee
+    }
+}
+
+impl Default for FeeEstimator {
+    fn default() -> Self {
+        Self::new(FeeEstimatorConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_for_picks_highest_bound_not_exceeding_fee() {
+        let estimator = FeeEstimator::default();
+        assert_eq!(estimator.bucket_for(0), 0);
+        assert_eq!(estimator.bucket_for(1), 0);
+        assert_eq!(estimator.bucket_for(estimator.bucket_bounds[1]), 1);
+    }
+
+    #[test]
+    fn test_insufficient_data_falls_back_to_base_fee() {
+        let estimator = FeeEstimator::default();
+        let market = FeeMarket::default();
+
+        assert_eq!(
+            estimator.estimate_priority_fee(&market, 3, 0.85),
+            market.base_fee
+        );
+    }
+
+    #[test]
+    fn test_high_tip_bucket_confirms_fast_and_clears_threshold() {
+        let mut estimator = FeeEstimator::default();
+        let market = FeeMarket::default();
+        let high_tip = estimator.bucket_bounds[10];
+
+        for _ in 0..20 {
+            estimator.record_confirmation(high_tip, 1);
+        }
+
+        assert_eq!(estimator.estimate_priority_fee(&market, 1, 0.85), high_tip);
+    }
+
+    #[test]
+    fn test_low_tip_bucket_confirms_slowly_so_low_depth_falls_back() {
+        let mut estimator = FeeEstimator::default();
+        let market = FeeMarket::default();
+        let low_tip = estimator.bucket_bounds[1];
+
+        // This bucket only ever confirms after 10+ blocks, so it should
+        // never clear the threshold for a 1-block target.
+        for _ in 0..20 {
+            estimator.record_confirmation(low_tip, 10);
+        }
+
+        assert_eq!(
+            estimator.estimate_priority_fee(&market, 1, 0.85),
+            market.base_fee
+        );
+        assert_eq!(estimator.estimate_priority_fee(&market, 10, 0.85), low_tip);
+    }
+
+    #[test]
+    fn test_estimate_clamps_target_blocks_to_cap() {
+        let mut estimator = FeeEstimator::default();
+        let market = FeeMarket::default();
+        let tip = estimator.bucket_bounds[5];
+
+        for _ in 0..20 {
+            estimator.record_confirmation(tip, 1);
+        }
+
+        let within_cap = estimator.estimate_priority_fee(&market, estimator.config.max_target_blocks, 0.85);
+        let beyond_cap = estimator.estimate_priority_fee(&market, estimator.config.max_target_blocks + 50, 0.85);
+        assert_eq!(within_cap, beyond_cap);
+    }
+}
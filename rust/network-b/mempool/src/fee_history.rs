@@ -0,0 +1,234 @@
+// EIP-1559-style fee history
+// Tracks a ring buffer of recent per-block fee data so wallets can pick a
+// priority fee that lands in a target block, mirroring `eth_feeHistory`.
+
+use std::collections::VecDeque;
+
+use coinject_core::Balance;
+
+use crate::fee_market::{ComputeUnits, FeeMarket, FeeMarketConfig};
+
+/// Longest window `FeeHistory` retains; `fee_history` clamps `block_count`
+/// to `1..=MAX_HISTORY_BLOCKS`.
+pub const MAX_HISTORY_BLOCKS: usize = 1024;
+
+/// One mined block's fee data, as recorded by `FeeHistory::record_block`.
+#[derive(Clone, Debug)]
+pub struct BlockFeeRecord {
+    pub base_fee: Balance,
+    pub transactions_in_block: usize,
+    pub max_transactions: usize,
+    /// Total compute units the block's transactions declared, the unit
+    /// `fullness_ratio` and the projected `next_base_fee` are now metered
+    /// in instead of the flat transaction count.
+    pub gas_used: ComputeUnits,
+    pub max_gas: ComputeUnits,
+    /// Priority fees paid in this block, kept sorted ascending so
+    /// `priority_fee_at_percentile` can index directly.
+    sorted_priority_fees: Vec<Balance>,
+}
+
+impl BlockFeeRecord {
+    pub fn new(
+        base_fee: Balance,
+        transactions_in_block: usize,
+        max_transactions: usize,
+        gas_used: ComputeUnits,
+        max_gas: ComputeUnits,
+        mut priority_fees: Vec<Balance>,
+    ) -> Self {
+        priority_fees.sort_unstable();
+        BlockFeeRecord {
+            base_fee,
+            transactions_in_block,
+            max_transactions,
+            gas_used,
+            max_gas,
+            sorted_priority_fees: priority_fees,
+        }
+    }
+
+    /// `gas_used / max_gas`, how full this block was.
+    pub fn fullness_ratio(&self) -> f64 {
+        if self.max_gas == 0 {
+            return 0.0;
+        }
+        self.gas_used as f64 / self.max_gas as f64
+    }
+
+    /// The priority fee at `percentile` (`0.0..=100.0`) among this block's
+    /// sorted tips, indexed at `ceil(pct/100 * (n-1))`. `None` for a block
+    /// with no transactions.
+    pub fn priority_fee_at_percentile(&self, percentile: f64) -> Option<Balance> {
+        let n = self.sorted_priority_fees.len();
+        if n == 0 {
+            return None;
+        }
+        let index = ((percentile / 100.0) * (n - 1) as f64).ceil() as usize;
+        Some(self.sorted_priority_fees[index.min(n - 1)])
+    }
+}
+
+/// One row of a `FeeHistory::fee_history` query result.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeeHistoryEntry {
+    pub base_fee: Balance,
+    pub fullness_ratio: f64,
+    /// This block's projected next base fee, via `simulate_next_base_fee`.
+    pub next_base_fee: Balance,
+    /// `(percentile, priority_fee)` pairs, in the order `percentiles` was
+    /// given to `fee_history`. Empty for a block with no transactions.
+    pub priority_fee_percentiles: Vec<(f64, Balance)>,
+}
+
+/// Ring buffer of recent `BlockFeeRecord`s, queried via `fee_history`.
+#[derive(Clone, Debug)]
+pub struct FeeHistory {
+    records: VecDeque<BlockFeeRecord>,
+    config: FeeMarketConfig,
+}
+
+impl FeeHistory {
+    pub fn new(config: FeeMarketConfig) -> Self {
+        FeeHistory {
+            records: VecDeque::new(),
+            config,
+        }
+    }
+
+    /// Record a mined block's fee data, evicting the oldest block once the
+    /// window exceeds `MAX_HISTORY_BLOCKS`.
+    pub fn record_block(&mut self, record: BlockFeeRecord) {
+        self.records.push_back(record);
+        while self.records.len() > MAX_HISTORY_BLOCKS {
+            self.records.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// `eth_feeHistory`-style query: for the last `block_count` blocks
+    /// (clamped to `1..=MAX_HISTORY_BLOCKS`, and to however many blocks are
+    /// actually recorded), the block's `base_fee`, fullness ratio,
+    /// projected next `base_fee`, and the priority fee at each requested
+    /// percentile.
+    pub fn fee_history(&self, block_count: usize, percentiles: &[f64]) -> Vec<FeeHistoryEntry> {
+        let block_count = block_count.clamp(1, MAX_HISTORY_BLOCKS).min(self.records.len());
+        let skip = self.records.len() - block_count;
+
+        self.records
+            .iter()
+            .skip(skip)
+            .map(|record| self.entry_for(record, percentiles))
+            .collect()
+    }
+
+    fn entry_for(&self, record: &BlockFeeRecord, percentiles: &[f64]) -> FeeHistoryEntry {
+        let priority_fee_percentiles = if record.sorted_priority_fees.is_empty() {
+            Vec::new()
+        } else {
+            percentiles
+                .iter()
+                .map(|&pct| (pct, record.priority_fee_at_percentile(pct).unwrap()))
+                .collect()
+        };
+
+        let market_at_block = FeeMarket::new_with_base_fee(self.config.clone(), record.base_fee);
+
+        FeeHistoryEntry {
+            base_fee: record.base_fee,
+            fullness_ratio: record.fullness_ratio(),
+            next_base_fee: market_at_block.simulate_next_base_fee(record.gas_used),
+            priority_fee_percentiles,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> FeeHistory {
+        FeeHistory::new(FeeMarketConfig::default())
+    }
+
+    fn record(base_fee: Balance, gas_used: ComputeUnits, tips: Vec<Balance>) -> BlockFeeRecord {
+        BlockFeeRecord::new(base_fee, gas_used as usize, 200, gas_used, 200, tips)
+    }
+
+    #[test]
+    fn test_fullness_ratio() {
+        let r = record(1000, 50, vec![]);
+        assert_eq!(r.fullness_ratio(), 0.25);
+    }
+
+    #[test]
+    fn test_percentile_on_empty_block_is_none() {
+        let r = record(1000, 0, vec![]);
+        assert_eq!(r.priority_fee_at_percentile(50.0), None);
+    }
+
+    #[test]
+    fn test_percentile_indexing() {
+        let r = record(1000, 4, vec![10, 20, 30, 40]);
+        assert_eq!(r.priority_fee_at_percentile(0.0), Some(10));
+        assert_eq!(r.priority_fee_at_percentile(100.0), Some(40));
+    }
+
+    #[test]
+    fn test_fee_history_clamps_block_count() {
+        let mut h = history();
+        for i in 0..5 {
+            h.record_block(record(1000 + i, 100, vec![5, 10]));
+        }
+
+        let rows = h.fee_history(0, &[50.0]);
+        assert_eq!(rows.len(), 1, "block_count clamps to at least 1");
+
+        let rows = h.fee_history(9999, &[50.0]);
+        assert_eq!(rows.len(), 5, "block_count clamps to what is actually recorded");
+    }
+
+    #[test]
+    fn test_fee_history_reports_percentiles_in_order() {
+        let mut h = history();
+        h.record_block(record(1000, 100, vec![10, 20, 30, 40, 50]));
+
+        let rows = h.fee_history(1, &[0.0, 50.0, 100.0]);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            rows[0].priority_fee_percentiles,
+            vec![(0.0, 10), (50.0, 30), (100.0, 50)]
+        );
+    }
+
+    #[test]
+    fn test_fee_history_empty_block_has_no_percentile_rows() {
+        let mut h = history();
+        h.record_block(record(1000, 0, vec![]));
+
+        let rows = h.fee_history(1, &[50.0]);
+        assert!(rows[0].priority_fee_percentiles.is_empty());
+    }
+
+    #[test]
+    fn test_fee_history_reports_fullness_and_next_base_fee() {
+        let mut h = history();
+        // Gas used at the config's own max_gas (30_000), so the block is
+        // both 100% full and over the config's target_gas (15_000).
+        h.record_block(BlockFeeRecord::new(1000, 1, 200, 30_000, 30_000, vec![1]));
+
+        let rows = h.fee_history(1, &[]);
+        assert_eq!(rows[0].fullness_ratio, 1.0);
+        assert!(
+            rows[0].next_base_fee > rows[0].base_fee,
+            "a fully congested block should project a higher next base fee"
+        );
+    }
+}
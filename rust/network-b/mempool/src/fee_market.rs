@@ -2,15 +2,72 @@
 // Adjusts base fee based on block congestion
 
 use coinject_core::Balance;
+use coinjecture_core::VerifyBudget;
 use serde::{Deserialize, Serialize};
 
+/// A transaction's declared execution cost, the unit `update_base_fee` now
+/// meters congestion in instead of a flat transaction count - a tiny
+/// transfer and a heavy subset-sum proof submission no longer weigh the
+/// same.
+pub type ComputeUnits = u64;
+
+/// A saturating fixed-point fraction stored scaled by `SCALE` (10^12). `update_base_fee` uses this instead of plain `i64` division
+/// so a small `delta / (target * denominator)` ratio doesn't truncate to
+/// zero and stall the fee near the floor, and so `base_fee * delta` can't
+/// silently overflow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fixed(i128);
+
+impl Fixed {
+    /// Fractional scale: `ONE` represents `1.0`.
+    const SCALE: i128 = 1_000_000_000_000; // 1e12, well within i128 headroom
+
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(Self::SCALE);
+
+    /// Build `numerator / denominator` as a `Fixed`, saturating to
+    /// `i128::MAX`/`MIN` rather than overflowing, and returning `ZERO` for
+    /// a zero denominator.
+    pub fn from_ratio(numerator: i128, denominator: i128) -> Fixed {
+        if denominator == 0 {
+            return Fixed::ZERO;
+        }
+        let scaled = numerator.saturating_mul(Self::SCALE);
+        Fixed(scaled.checked_div(denominator).unwrap_or(0))
+    }
+
+    /// Saturating fixed-point addition.
+    pub fn add(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_add(other.0))
+    }
+
+    /// Multiply this factor by an integer, rounding to the nearest integer
+    /// (round-half-up, ties away from zero) rather than truncating, and
+    /// saturating to `i128::MIN`/`MAX`.
+    pub fn mul_round(self, value: i128) -> i128 {
+        let product = self.0.saturating_mul(value);
+        let half = Self::SCALE / 2;
+        if product >= 0 {
+            product.saturating_add(half)
+        } else {
+            product.saturating_sub(half)
+        } / Self::SCALE
+    }
+}
+
 /// Fee market configuration
 #[derive(Clone, Debug)]
 pub struct FeeMarketConfig {
-    /// Target transactions per block
+    /// Target transactions per block (kept on `FeeMarket`; congestion and
+    /// `FeeHistory` fullness ratios are metered in gas, below)
     pub target_transactions: usize,
     /// Maximum transactions per block
     pub max_transactions: usize,
+    /// Target compute units (gas) per block
+    pub target_gas: ComputeUnits,
+    /// Maximum compute units (gas) per block - the elasticity multiplier
+    /// over `target_gas`, same role as EIP-1559's 2x gas limit
+    pub max_gas: ComputeUnits,
     /// Initial base fee
     pub initial_base_fee: Balance,
     /// Maximum base fee change per block (12.5% like EIP-1559)
@@ -24,6 +81,8 @@ impl Default for FeeMarketConfig {
         FeeMarketConfig {
             target_transactions: 100,
             max_transactions: 200,
+            target_gas: 15_000,
+            max_gas: 30_000,
             initial_base_fee: 1000,
             max_change_denominator: 8, // 1/8 = 12.5%
             min_base_fee: 100,
@@ -55,38 +114,63 @@ impl FeeMarket {
         }
     }
 
-    /// Update base fee after a block is mined
+    /// Build a market pinned to `base_fee` rather than `config`'s initial
+    /// fee, used by `fee_history::FeeHistory` to replay `simulate_next_base_fee`
+    /// from a past block's own base fee instead of the market's current one.
+    pub fn new_with_base_fee(config: FeeMarketConfig, base_fee: Balance) -> Self {
+        FeeMarket {
+            base_fee,
+            target_transactions: config.target_transactions,
+            max_transactions: config.max_transactions,
+            config,
+        }
+    }
+
+    /// Update base fee after a block is mined, based on the block's total
+    /// compute units used rather than its transaction count - a block full
+    /// of cheap transfers and a block half-full of subset-sum proof
+    /// submissions are weighed by actual verification cost, not a head
+    /// count.
     /// Based on EIP-1559 formula: base_fee_delta = base_fee * gas_used_delta / target / max_change_denominator
-    pub fn update_base_fee(&mut self, transactions_in_block: usize) {
-        let target = self.target_transactions as i64;
-        let actual = transactions_in_block as i64;
+    ///
+    /// All intermediate arithmetic runs in `i128` via `Fixed`, so neither a
+    /// maliciously large `base_fee` nor a large `gas_used` can overflow the
+    /// way the old plain-`i64` multiply could. The multiplicative factor
+    /// `1 + delta / (target * denominator)` is computed in `Fixed`-point
+    /// rather than truncating integer division, so a small delta near the
+    /// fee floor still nudges the fee instead of rounding away to a no-op
+    /// every block.
+    pub fn update_base_fee(&mut self, gas_used: ComputeUnits) {
+        let target = self.config.target_gas as i128;
+        let actual = gas_used as i128;
         let delta = actual - target;
 
-        if delta == 0 {
-            return; // No change if exactly at target
+        if delta == 0 || target == 0 {
+            // No adjustment if exactly at target, but the floor still holds.
+            self.base_fee = self.base_fee.max(self.config.min_base_fee);
+            return;
         }
 
-        let base = self.base_fee as i64;
-        let denominator = self.config.max_change_denominator as i64;
-
-        // Calculate change: base_fee * delta / target / denominator
-        let numerator = base * delta;
-        let change = numerator / target / denominator;
+        let base = self.base_fee as i128;
+        let denominator = self.config.max_change_denominator as i128;
 
-        // Apply change
-        let new_base_fee = (base + change).max(self.config.min_base_fee as i64);
-        self.base_fee = new_base_fee as Balance;
+        let factor = Fixed::ONE.add(Fixed::from_ratio(delta, target.saturating_mul(denominator)));
+        let new_base_fee = factor
+            .mul_round(base)
+            .max(self.config.min_base_fee as i128);
+        self.base_fee = new_base_fee.clamp(0, Balance::MAX as i128) as Balance;
     }
 
     /// Calculate total fee for a transaction
-    /// total_fee = base_fee + priority_fee
+    /// total_fee = base_fee + priority_fee, saturating rather than
+    /// overflowing on a maliciously large `priority_fee`.
     pub fn calculate_total_fee(&self, priority_fee: Balance) -> Balance {
-        self.base_fee + priority_fee
+        self.base_fee.saturating_add(priority_fee)
     }
 
     /// Check if transaction fee meets minimum requirements
     pub fn validate_fee(&self, total_fee: Balance, priority_fee: Balance) -> bool {
-        total_fee >= self.base_fee + priority_fee && total_fee >= self.config.min_base_fee
+        total_fee >= self.base_fee.saturating_add(priority_fee) && total_fee >= self.config.min_base_fee
     }
 
     /// Get the portion of fee that goes to miner
@@ -102,14 +186,31 @@ impl FeeMarket {
         total_fee.saturating_sub(miner)
     }
 
-    /// Simulate next base fee given expected block fullness
-    pub fn simulate_next_base_fee(&self, expected_transactions: usize) -> Balance {
+    /// Simulate next base fee given an expected block gas usage
+    pub fn simulate_next_base_fee(&self, expected_gas_used: ComputeUnits) -> Balance {
         let mut simulated = self.clone();
-        simulated.update_base_fee(expected_transactions);
+        simulated.update_base_fee(expected_gas_used);
         simulated.base_fee
     }
 }
 
+/// Fee-per-compute-unit, so the mempool can rank transactions by price
+/// density rather than absolute tip - the same move Solana made when it
+/// began prioritizing by compute-unit price. `compute_units` is clamped to
+/// at least 1 so a (disallowed) zero-cost transaction prices as its full
+/// tip rather than dividing by zero.
+pub fn priority_by_compute_unit_price(priority_fee: Balance, compute_units: ComputeUnits) -> f64 {
+    priority_fee as f64 / compute_units.max(1) as f64
+}
+
+/// Compute units for a subset-sum verification: scales with both the
+/// op budget a verifier must spend (`VerifyBudget::max_ops`) and the
+/// element count the solution indexes into, since a larger problem costs
+/// more per operation to even read.
+pub fn compute_units_for_subset_sum(budget: &VerifyBudget, element_count: usize) -> ComputeUnits {
+    budget.max_ops.saturating_add(element_count as u64)
+}
+
 impl Default for FeeMarket {
     fn default() -> Self {
         Self::new(FeeMarketConfig::default())
@@ -157,8 +258,8 @@ mod tests {
         let mut market = FeeMarket::default();
         let initial_fee = market.base_fee;
 
-        // Block is 50% over target (150 txs vs 100 target)
-        market.update_base_fee(150);
+        // Block used 50% more gas than target (22_500 vs 15_000 target)
+        market.update_base_fee(22_500);
 
         assert!(
             market.base_fee > initial_fee,
@@ -171,8 +272,8 @@ mod tests {
         let mut market = FeeMarket::default();
         let initial_fee = market.base_fee;
 
-        // Block is 50% under target (50 txs vs 100 target)
-        market.update_base_fee(50);
+        // Block used 50% less gas than target (7_500 vs 15_000 target)
+        market.update_base_fee(7_500);
 
         assert!(
             market.base_fee < initial_fee,
@@ -185,8 +286,8 @@ mod tests {
         let mut market = FeeMarket::default();
         let initial_fee = market.base_fee;
 
-        // Block is exactly at target
-        market.update_base_fee(100);
+        // Block used exactly the target amount of gas
+        market.update_base_fee(15_000);
 
         assert_eq!(
             market.base_fee, initial_fee,
@@ -244,11 +345,11 @@ mod tests {
         let current_fee = market.base_fee;
 
         // Simulate high congestion
-        let next_fee_high = market.simulate_next_base_fee(180);
+        let next_fee_high = market.simulate_next_base_fee(27_000);
         assert!(next_fee_high > current_fee);
 
         // Simulate low congestion
-        let next_fee_low = market.simulate_next_base_fee(20);
+        let next_fee_low = market.simulate_next_base_fee(3_000);
         assert!(next_fee_low < current_fee);
 
         // Original market should be unchanged
@@ -260,11 +361,11 @@ mod tests {
         let mut market = FeeMarket::default();
         let initial = market.base_fee;
 
-        // Full blocks should gradually increase fee
-        market.update_base_fee(200); // Max capacity
+        // Max-gas blocks should gradually increase fee
+        market.update_base_fee(30_000); // Max capacity
         let after_one = market.base_fee;
 
-        market.update_base_fee(200);
+        market.update_base_fee(30_000);
         let after_two = market.base_fee;
 
         // Each step should increase
@@ -275,4 +376,88 @@ mod tests {
         let max_change = initial / market.config.max_change_denominator as Balance;
         assert!((after_one - initial) <= max_change * 2); // Allow some rounding
     }
+
+    #[test]
+    fn test_fixed_from_ratio_rounds_half_up_instead_of_truncating() {
+        // 3 * (1 + 5/(10*2)) = 3 * 1.25 = 3.75, rounds up to 4 rather than
+        // truncating the way plain i64 division would.
+        let factor = Fixed::ONE.add(Fixed::from_ratio(5, 10 * 2));
+        assert_eq!(factor.mul_round(3), 4);
+    }
+
+    #[test]
+    fn test_fixed_from_ratio_saturates_on_overflow_instead_of_panicking() {
+        // A maliciously huge numerator/value pair would overflow a plain
+        // multiply; `Fixed` must saturate instead of panicking.
+        let factor = Fixed::from_ratio(i128::MAX, 1);
+        assert!(factor.mul_round(i128::MAX) > 0);
+    }
+
+    #[test]
+    fn test_base_fee_does_not_stall_near_floor_under_small_congestion() {
+        // target_gas=10, denominator=2: a block at 15 gas used is 50% over
+        // target, but with a starting base fee of 3 the old plain-i64 math
+        // (3 * 5 / 10 / 2 == 0) would truncate to zero every block and
+        // never move off the floor. Fixed-point rounding should instead
+        // produce a strictly increasing sequence.
+        let config = FeeMarketConfig {
+            target_gas: 10,
+            max_gas: 20,
+            initial_base_fee: 3,
+            max_change_denominator: 2,
+            min_base_fee: 1,
+            ..FeeMarketConfig::default()
+        };
+        let mut market = FeeMarket::new(config);
+
+        let mut fees = vec![market.base_fee];
+        for _ in 0..5 {
+            market.update_base_fee(15);
+            fees.push(market.base_fee);
+        }
+
+        for pair in fees.windows(2) {
+            assert!(
+                pair[1] > pair[0],
+                "base fee stalled at {} instead of increasing: {:?}",
+                pair[0],
+                fees
+            );
+        }
+    }
+
+    #[test]
+    fn test_priority_by_compute_unit_price_ranks_cheaper_solution_higher() {
+        // Same tip, but one transaction declares far fewer compute units -
+        // it should price denser (more fee per unit of work).
+        let cheap = priority_by_compute_unit_price(1000, 100);
+        let expensive = priority_by_compute_unit_price(1000, 10_000);
+        assert!(cheap > expensive);
+    }
+
+    #[test]
+    fn test_priority_by_compute_unit_price_zero_units_does_not_divide_by_zero() {
+        assert_eq!(priority_by_compute_unit_price(1000, 0), 1000.0);
+    }
+
+    #[test]
+    fn test_compute_units_for_subset_sum_scales_with_budget_and_elements() {
+        let small_budget = VerifyBudget {
+            max_ops: 1_000,
+            max_duration_ms: 100,
+            max_memory_bytes: 1024,
+        };
+        let large_budget = VerifyBudget {
+            max_ops: 100_000,
+            max_duration_ms: 100,
+            max_memory_bytes: 1024,
+        };
+
+        assert!(
+            compute_units_for_subset_sum(&large_budget, 32) > compute_units_for_subset_sum(&small_budget, 32)
+        );
+        assert!(
+            compute_units_for_subset_sum(&small_budget, 64) > compute_units_for_subset_sum(&small_budget, 8)
+        );
+    }
 }
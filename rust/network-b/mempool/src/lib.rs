@@ -4,7 +4,13 @@
 pub mod pool;
 pub mod marketplace;
 pub mod fee_market;
+pub mod fee_history;
+pub mod fee_estimator;
+pub mod net;
 
 pub use pool::*;
 pub use marketplace::*;
 pub use fee_market::*;
+pub use fee_history::*;
+pub use fee_estimator::*;
+pub use net::*;
@@ -0,0 +1,303 @@
+// P2P wire protocol framing.
+//
+// Every gossiped message (headers, problems, solutions, filter requests)
+// shares one frame: a 4-byte magic, a 1-byte command tag, the payload
+// length, and a checksum over the payload, followed by the payload itself.
+// The payload bytes are produced by the caller via `coinjecture_core::codec`
+// (e.g. `encode_msgpack(&header)`); this module only frames and deframes
+// opaque bytes, the way rust-bitcoin's network message layer separates
+// framing from payload decoding.
+
+use coinjecture_core::hash::sha256;
+use std::io::{self, ErrorKind, Read};
+
+/// Magic bytes identifying a COINjecture wire message, checked before any
+/// other field is trusted.
+pub const MAGIC: [u8; 4] = *b"COIN";
+
+/// Fixed frame header size: magic(4) + command(1) + length(4) + checksum(4).
+const HEADER_LEN: usize = 4 + 1 + 4 + 4;
+
+/// Gossiped message kinds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    Header,
+    Problem,
+    Solution,
+    GetFilters,
+    Filter,
+    Inv,
+    Ping,
+}
+
+impl Command {
+    fn to_u8(self) -> u8 {
+        match self {
+            Command::Header => 0,
+            Command::Problem => 1,
+            Command::Solution => 2,
+            Command::GetFilters => 3,
+            Command::Filter => 4,
+            Command::Inv => 5,
+            Command::Ping => 6,
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Command::Header),
+            1 => Some(Command::Problem),
+            2 => Some(Command::Solution),
+            3 => Some(Command::GetFilters),
+            4 => Some(Command::Filter),
+            5 => Some(Command::Inv),
+            6 => Some(Command::Ping),
+            _ => None,
+        }
+    }
+}
+
+/// One framed wire message: a command tag plus its opaque payload bytes
+/// (already msgpack-encoded by the caller via `coinjecture_core::codec`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WireMessage {
+    pub command: Command,
+    pub payload: Vec<u8>,
+}
+
+impl WireMessage {
+    pub fn new(command: Command, payload: Vec<u8>) -> Self {
+        WireMessage { command, payload }
+    }
+}
+
+/// Checksum over a payload: the first four bytes of `sha256(sha256(payload))`.
+fn checksum(payload: &[u8]) -> [u8; 4] {
+    let double_hashed = sha256(&sha256(payload));
+    [double_hashed[0], double_hashed[1], double_hashed[2], double_hashed[3]]
+}
+
+/// Frame a message into its wire bytes.
+pub fn encode(msg: &WireMessage) -> Vec<u8> {
+    let mut out = Vec::with_capacity(HEADER_LEN + msg.payload.len());
+    out.extend_from_slice(&MAGIC);
+    out.push(msg.command.to_u8());
+    out.extend_from_slice(&(msg.payload.len() as u32).to_le_bytes());
+    out.extend_from_slice(&checksum(&msg.payload));
+    out.extend_from_slice(&msg.payload);
+    out
+}
+
+/// Decode a single complete frame from `data`, rejecting a declared
+/// payload length over `max_payload_len` or a checksum mismatch.
+/// Returns the decoded message and the number of bytes consumed.
+pub fn decode(data: &[u8], max_payload_len: u32) -> Result<(WireMessage, usize), String> {
+    if data.len() < HEADER_LEN {
+        return Err("frame shorter than header".to_string());
+    }
+
+    if data[0..4] != MAGIC {
+        return Err("bad magic".to_string());
+    }
+
+    let command = Command::from_u8(data[4]).ok_or_else(|| format!("unknown command tag {}", data[4]))?;
+    let payload_len = u32::from_le_bytes(data[5..9].try_into().unwrap());
+    if payload_len > max_payload_len {
+        return Err(format!(
+            "payload length {} exceeds cap {}",
+            payload_len, max_payload_len
+        ));
+    }
+
+    let declared_checksum: [u8; 4] = data[9..13].try_into().unwrap();
+    let frame_len = HEADER_LEN + payload_len as usize;
+    if data.len() < frame_len {
+        return Err("frame shorter than declared payload length".to_string());
+    }
+
+    let payload = data[HEADER_LEN..frame_len].to_vec();
+    if checksum(&payload) != declared_checksum {
+        return Err("checksum mismatch".to_string());
+    }
+
+    Ok((WireMessage::new(command, payload), frame_len))
+}
+
+/// Buffers bytes read from `R` and yields complete `WireMessage`s one at a
+/// time, even when a single `read` call returns a partial frame. Mirrors
+/// rust-bitcoin's `StreamReader`: the caller drives I/O by repeatedly
+/// calling `next_message`, which blocks only long enough to fill in the
+/// bytes still missing from the frame currently being assembled.
+pub struct StreamReader<R: Read> {
+    reader: R,
+    buffer: Vec<u8>,
+    max_payload_len: u32,
+}
+
+impl<R: Read> StreamReader<R> {
+    pub fn new(reader: R, max_payload_len: u32) -> Self {
+        StreamReader {
+            reader,
+            buffer: Vec::new(),
+            max_payload_len,
+        }
+    }
+
+    /// Read the next complete message, blocking on underlying reads as
+    /// needed. Returns `Ok(None)` on a clean EOF with no partial frame
+    /// buffered.
+    pub fn next_message(&mut self) -> Result<Option<WireMessage>, String> {
+        loop {
+            if self.buffer.len() >= HEADER_LEN {
+                if self.buffer[0..4] != MAGIC {
+                    return Err("bad magic".to_string());
+                }
+                let payload_len = u32::from_le_bytes(self.buffer[5..9].try_into().unwrap());
+                if payload_len > self.max_payload_len {
+                    return Err(format!(
+                        "payload length {} exceeds cap {}",
+                        payload_len, self.max_payload_len
+                    ));
+                }
+
+                let frame_len = HEADER_LEN + payload_len as usize;
+                if self.buffer.len() >= frame_len {
+                    let (msg, consumed) = decode(&self.buffer, self.max_payload_len)?;
+                    self.buffer.drain(..consumed);
+                    return Ok(Some(msg));
+                }
+                // Header is in; still waiting on the rest of the payload.
+            }
+
+            let mut chunk = [0u8; 4096];
+            match self.reader.read(&mut chunk) {
+                Ok(0) if self.buffer.is_empty() => return Ok(None),
+                Ok(0) => return Err("stream ended mid-frame".to_string()),
+                Ok(n) => self.buffer.extend_from_slice(&chunk[..n]),
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(format!("read failed: {}", err)),
+            }
+        }
+    }
+}
+
+/// Read a single message from any `Read`, for callers that don't need a
+/// persistent `StreamReader` across many reads.
+pub fn read_message<R: Read>(reader: R, max_payload_len: u32) -> io::Result<Option<WireMessage>> {
+    let mut stream = StreamReader::new(reader, max_payload_len);
+    stream
+        .next_message()
+        .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip_case(command: Command, payload: Vec<u8>) {
+        let msg = WireMessage::new(command, payload);
+        let bytes = encode(&msg);
+        let (decoded, consumed) = decode(&bytes, u32::MAX).unwrap();
+        assert_eq!(decoded, msg);
+        assert_eq!(consumed, bytes.len());
+    }
+
+    #[test]
+    fn test_roundtrip_every_command_with_various_payload_sizes() {
+        let commands = [
+            Command::Header,
+            Command::Problem,
+            Command::Solution,
+            Command::GetFilters,
+            Command::Filter,
+            Command::Inv,
+            Command::Ping,
+        ];
+
+        for command in commands {
+            for len in [0usize, 1, 32, 255, 1000] {
+                let payload: Vec<u8> = (0..len).map(|i| (i % 256) as u8).collect();
+                roundtrip_case(command, payload);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_rejects_bad_magic() {
+        let mut bytes = encode(&WireMessage::new(Command::Ping, vec![1, 2, 3]));
+        bytes[0] ^= 0xFF;
+        assert!(decode(&bytes, u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_corrupted_payload() {
+        let mut bytes = encode(&WireMessage::new(Command::Solution, vec![1, 2, 3]));
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        assert!(decode(&bytes, u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_oversized_payload_length() {
+        let bytes = encode(&WireMessage::new(Command::Filter, vec![0u8; 100]));
+        assert!(decode(&bytes, 10).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_unknown_command() {
+        let mut bytes = encode(&WireMessage::new(Command::Ping, vec![]));
+        bytes[4] = 0xFF;
+        assert!(decode(&bytes, u32::MAX).is_err());
+    }
+
+    /// Feeds a full stream of several framed messages to the reader one
+    /// byte at a time, simulating a TCP socket that never returns a whole
+    /// frame in a single read.
+    struct OneByteAtATime(std::collections::VecDeque<u8>);
+
+    impl Read for OneByteAtATime {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.pop_front() {
+                Some(byte) => {
+                    buf[0] = byte;
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_reader_reassembles_partial_frames() {
+        let messages = vec![
+            WireMessage::new(Command::Header, vec![1, 2, 3]),
+            WireMessage::new(Command::Ping, vec![]),
+            WireMessage::new(Command::Solution, (0..50).collect()),
+        ];
+
+        let mut bytes = Vec::new();
+        for msg in &messages {
+            bytes.extend(encode(msg));
+        }
+
+        let source = OneByteAtATime(bytes.into_iter().collect());
+        let mut reader = StreamReader::new(source, u32::MAX);
+
+        for expected in &messages {
+            let decoded = reader.next_message().unwrap().expect("message present");
+            assert_eq!(&decoded, expected);
+        }
+        assert!(reader.next_message().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stream_reader_rejects_mid_frame_eof() {
+        let full_frame = encode(&WireMessage::new(Command::Inv, vec![9u8; 10]));
+        let truncated = full_frame[..full_frame.len() - 3].to_vec();
+
+        let source = OneByteAtATime(truncated.into_iter().collect());
+        let mut reader = StreamReader::new(source, u32::MAX);
+
+        assert!(reader.next_message().is_err());
+    }
+}
@@ -10,7 +10,9 @@
 //
 // Reference: COINjecture White Paper v2.3, Mathematical Proof
 
+use crate::confidential::{pool_generators, sample_blinding, PedersenCommitment, RangeProof};
 use coinject_core::{Address, Balance, DimensionalPool, Hash};
+use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 use sled::Db;
 use std::sync::Arc;
@@ -71,10 +73,63 @@ pub struct PoolSwapRecord {
     pub amount_out: Balance,
     /// Swap ratio (amount_out / amount_in)
     pub swap_ratio: f64,
+    /// Instantaneous pre-trade price (`y / x` on the constant-product curve)
+    pub marginal_price: f64,
+    /// Fractional degradation of the realized rate versus `marginal_price`
+    pub price_impact: f64,
     /// Block height when swap occurred
     pub block_height: u64,
 }
 
+/// Preview of a swap's output and pricing on the constant-product curve,
+/// returned by both `execute_swap` (after mutating pool state) and
+/// `quote_swap` (read-only).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SwapQuote {
+    /// Tokens the trade would yield (or yielded) from `pool_to`
+    pub amount_out: Balance,
+    /// Instantaneous pre-trade price `y / x`, in units of pool_to per pool_from
+    pub marginal_price: f64,
+    /// Realized average price `amount_out / amount_in` for this trade
+    pub execution_price: f64,
+    /// Fractional degradation of `execution_price` versus `marginal_price`;
+    /// 0 for an infinitesimal trade, growing toward 1 as the trade drains
+    /// the destination pool
+    pub price_impact: f64,
+}
+
+/// A swap whose amounts are hidden behind Pedersen commitments with
+/// Bulletproof range proofs rather than stored as cleartext `Balance`s.
+#[derive(Clone, Debug)]
+pub struct ConfidentialSwapRecord {
+    pub tx_hash: Hash,
+    pub from: Address,
+    pub pool_from: DimensionalPool,
+    pub pool_to: DimensionalPool,
+    pub commitment_in: PedersenCommitment,
+    pub commitment_out: PedersenCommitment,
+    pub range_proof_in: RangeProof,
+    pub range_proof_out: RangeProof,
+    pub block_height: u64,
+}
+
+/// Check that committed amounts conserve value across a swap. With
+/// `ratio = D_from/D_to` rationalized to `numerator/denominator` (to avoid
+/// dividing a commitment), verifies the homomorphic form of
+/// `amount_in * denominator == (amount_out + fee) * numerator` without
+/// revealing either amount: `C_in^denominator == (C_out · C_fee)^numerator`.
+pub fn verify_conservation(
+    commitment_in: &PedersenCommitment,
+    commitment_out_plus_fee: &PedersenCommitment,
+    ratio: f64,
+) -> bool {
+    const SCALE: u64 = 1_000_000;
+    let numerator = (ratio * SCALE as f64).round() as u64;
+    let denominator = SCALE;
+
+    commitment_in.scale(denominator) == commitment_out_plus_fee.scale(numerator)
+}
+
 /// Dimensional Pool State Manager
 pub struct DimensionalPoolState {
     db: Arc<Db>,
@@ -137,8 +192,12 @@ impl DimensionalPoolState {
         Ok(())
     }
 
-    /// Execute pool swap with exponential dimensional ratios
-    /// Implements: amount_out = amount_in × (D_from / D_to)
+    /// Execute a pool swap on a constant-product curve `x·y = k`, with each
+    /// pool's effective reserve taken as `liquidity · D_n` so the dimensional
+    /// factors set the curve's price at equilibrium rather than a fixed
+    /// exchange rate. Larger trades move further along the curve and get
+    /// progressively worse rates; see `quote_swap` to preview this without
+    /// mutating state.
     pub fn execute_swap(
         &self,
         pool_from: DimensionalPool,
@@ -146,7 +205,7 @@ impl DimensionalPoolState {
         amount_in: Balance,
         min_amount_out: Balance,
         block_height: u64,
-    ) -> Result<Balance, String> {
+    ) -> Result<SwapQuote, String> {
         // Get pool liquidities
         let mut liquidity_from = self.get_pool_liquidity(&pool_from)
             .ok_or("Source pool not found")?;
@@ -159,35 +218,179 @@ impl DimensionalPoolState {
                 liquidity_from.liquidity, amount_in));
         }
 
-        // Calculate swap ratio using dimensional factors
-        // Ratio = D_from / D_to (exponential scaling)
-        let swap_ratio = liquidity_from.dimensional_factor / liquidity_to.dimensional_factor;
-        let amount_out = (amount_in as f64 * swap_ratio) as Balance;
+        let quote = Self::quote_from_liquidity(&liquidity_from, &liquidity_to, amount_in);
 
         // Check slippage protection
-        if amount_out < min_amount_out {
+        if quote.amount_out < min_amount_out {
             return Err(format!("Slippage exceeded: got {}, minimum {}",
-                amount_out, min_amount_out));
+                quote.amount_out, min_amount_out));
         }
 
         // Check destination pool has enough liquidity
+        if liquidity_to.liquidity < quote.amount_out {
+            return Err(format!("Insufficient liquidity in destination pool: has {}, needs {}",
+                liquidity_to.liquidity, quote.amount_out));
+        }
+
+        // Update pool liquidities: amount_in is deposited into pool_from,
+        // quote.amount_out is paid out of pool_to.
+        liquidity_from.liquidity += amount_in;
+        liquidity_from.last_update_height = block_height;
+
+        liquidity_to.liquidity -= quote.amount_out;
+        liquidity_to.last_update_height = block_height;
+
+        // Save updated pools
+        self.save_pool_liquidity(&liquidity_from)?;
+        self.save_pool_liquidity(&liquidity_to)?;
+
+        Ok(quote)
+    }
+
+    /// Preview a swap's output, marginal price, and price impact on the
+    /// constant-product curve without mutating pool state.
+    pub fn quote_swap(
+        &self,
+        pool_from: DimensionalPool,
+        pool_to: DimensionalPool,
+        amount_in: Balance,
+    ) -> Result<SwapQuote, String> {
+        let liquidity_from = self.get_pool_liquidity(&pool_from)
+            .ok_or("Source pool not found")?;
+        let liquidity_to = self.get_pool_liquidity(&pool_to)
+            .ok_or("Destination pool not found")?;
+
+        Ok(Self::quote_from_liquidity(&liquidity_from, &liquidity_to, amount_in))
+    }
+
+    /// Constant-product quote shared by `execute_swap` and `quote_swap`:
+    /// `amount_out = (y · dx) / (x + dx)` over effective reserves
+    /// `x = liquidity_from · D_from`, `y = liquidity_to · D_to`.
+    fn quote_from_liquidity(
+        liquidity_from: &PoolLiquidity,
+        liquidity_to: &PoolLiquidity,
+        amount_in: Balance,
+    ) -> SwapQuote {
+        let x = liquidity_from.liquidity as f64 * liquidity_from.dimensional_factor;
+        let y = liquidity_to.liquidity as f64 * liquidity_to.dimensional_factor;
+        let dx = amount_in as f64;
+
+        let marginal_price = if x > 0.0 { y / x } else { 0.0 };
+        let amount_out = if x + dx > 0.0 {
+            ((y * dx) / (x + dx)) as Balance
+        } else {
+            0
+        };
+
+        let execution_price = if amount_in > 0 {
+            amount_out as f64 / amount_in as f64
+        } else {
+            marginal_price
+        };
+        let price_impact = if marginal_price > 0.0 {
+            ((marginal_price - execution_price) / marginal_price).max(0.0)
+        } else {
+            0.0
+        };
+
+        SwapQuote {
+            amount_out,
+            marginal_price,
+            execution_price,
+            price_impact,
+        }
+    }
+
+    /// Confidential counterpart to `execute_swap`: the swapped amounts are
+    /// hidden behind Pedersen commitments with Bulletproof range proofs
+    /// instead of being written to the record in the clear, but pool
+    /// liquidity is updated exactly the same way `execute_swap` updates it —
+    /// `amount_in`/`amount_out` are only ever used here to move `liquidity`,
+    /// never written to the returned record. Callers reconcile conservation
+    /// out-of-band via `verify_conservation` against the committed fee,
+    /// since the record carries only commitments.
+    ///
+    /// NOTE: conservation here is still checked against the fixed
+    /// `D_from/D_to` ratio, not `execute_swap`'s constant-product curve —
+    /// callers must get `amount_out` for a confidential swap from that fixed
+    /// ratio rather than from `quote_swap`, or `verify_conservation` will
+    /// reject it. Moving this onto the AMM curve needs the price impact
+    /// blended into the conservation check, which is follow-up work.
+    pub fn execute_swap_confidential(
+        &self,
+        pool_from: DimensionalPool,
+        pool_to: DimensionalPool,
+        amount_in: Balance,
+        amount_out: Balance,
+        block_height: u64,
+        tx_hash: Hash,
+        from: Address,
+    ) -> Result<ConfidentialSwapRecord, String> {
+        let mut liquidity_from = self.get_pool_liquidity(&pool_from)
+            .ok_or("Source pool not found")?;
+        let mut liquidity_to = self.get_pool_liquidity(&pool_to)
+            .ok_or("Destination pool not found")?;
+
+        if liquidity_from.liquidity < amount_in {
+            return Err(format!("Insufficient liquidity in source pool: has {}, needs {}",
+                liquidity_from.liquidity, amount_in));
+        }
         if liquidity_to.liquidity < amount_out {
             return Err(format!("Insufficient liquidity in destination pool: has {}, needs {}",
                 liquidity_to.liquidity, amount_out));
         }
 
-        // Update pool liquidities
-        liquidity_from.liquidity -= amount_in;
+        let gens_from = pool_generators(pool_from);
+        let gens_to = pool_generators(pool_to);
+
+        // The blinding factors must satisfy `blinding_in * denominator ==
+        // blinding_out * numerator` as integers (exponents only repeat
+        // modulo the group order) for `verify_conservation` to hold on the
+        // returned commitments; see that function's doc comment for the
+        // numerator/denominator scaling. Scaling one random factor by each
+        // side's multiplier makes both products equal.
+        let ratio = self.get_dimensional_factor(pool_from) / self.get_dimensional_factor(pool_to);
+        const SCALE: u64 = 1_000_000;
+        let numerator = BigUint::from((ratio * SCALE as f64).round() as u64);
+        let denominator = BigUint::from(SCALE);
+
+        let shared_blinding = sample_blinding();
+        let blinding_in = &shared_blinding * &numerator;
+        let blinding_out = &shared_blinding * &denominator;
+
+        let commitment_in = PedersenCommitment::commit(amount_in, &blinding_in, &gens_from);
+        let commitment_out = PedersenCommitment::commit(amount_out, &blinding_out, &gens_to);
+
+        let range_proof_in = RangeProof::prove(amount_in, &gens_from, pool_from);
+        let range_proof_out = RangeProof::prove(amount_out, &gens_to, pool_to);
+
+        if !range_proof_in.verify(&gens_from, pool_from) || !range_proof_out.verify(&gens_to, pool_to) {
+            return Err("range proof generation produced an invalid proof".to_string());
+        }
+
+        // Update pool liquidities in the clear, mirroring `execute_swap`:
+        // amount_in is deposited into pool_from, amount_out is paid out of
+        // pool_to. Only the amounts themselves stay hidden in the record.
+        liquidity_from.liquidity += amount_in;
         liquidity_from.last_update_height = block_height;
 
         liquidity_to.liquidity -= amount_out;
         liquidity_to.last_update_height = block_height;
 
-        // Save updated pools
         self.save_pool_liquidity(&liquidity_from)?;
         self.save_pool_liquidity(&liquidity_to)?;
 
-        Ok(amount_out)
+        Ok(ConfidentialSwapRecord {
+            tx_hash,
+            from,
+            pool_from,
+            pool_to,
+            commitment_in,
+            commitment_out,
+            range_proof_in,
+            range_proof_out,
+            block_height,
+        })
     }
 
     /// Calculate dimensional factor: D_n = e^(-η·τ_n)
@@ -224,7 +427,8 @@ impl DimensionalPoolState {
             .unwrap_or(0.0)
     }
 
-    /// Record swap transaction
+    /// Record a swap transaction, including the pricing `quote` it executed
+    /// at (see `execute_swap`/`quote_swap`).
     pub fn record_swap(
         &self,
         tx_hash: Hash,
@@ -232,19 +436,19 @@ impl DimensionalPoolState {
         pool_from: DimensionalPool,
         pool_to: DimensionalPool,
         amount_in: Balance,
-        amount_out: Balance,
+        quote: SwapQuote,
         block_height: u64,
     ) -> Result<(), String> {
-        let swap_ratio = (amount_out as f64) / (amount_in as f64);
-
         let swap_record = PoolSwapRecord {
             tx_hash,
             from,
             pool_from,
             pool_to,
             amount_in,
-            amount_out,
-            swap_ratio,
+            amount_out: quote.amount_out,
+            swap_ratio: quote.execution_price,
+            marginal_price: quote.marginal_price,
+            price_impact: quote.price_impact,
             block_height,
         };
 
@@ -308,6 +512,40 @@ impl DimensionalPoolState {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_verify_conservation_holds_only_when_blinding_is_balanced() {
+        use num_bigint::BigUint;
+
+        let gens = pool_generators(DimensionalPool::D1);
+        let fee_gens = pool_generators(DimensionalPool::D2);
+
+        let ratio = 2.0;
+        let amount_in = 2000u64;
+        let amount_out = 900u64;
+        let fee = 100u64; // amount_in == (amount_out + fee) * ratio
+
+        const SCALE: u64 = 1_000_000;
+        let numerator = BigUint::from((ratio * SCALE as f64).round() as u64);
+        let denominator = BigUint::from(SCALE);
+
+        // A real swap must choose its blinding factors so the scaled
+        // commitments line up: `blinding_in * denominator ==
+        // blinding_out * numerator` as integers.
+        let shared_blinding = sample_blinding();
+        let blinding_in = &shared_blinding * &numerator;
+        let blinding_out = &shared_blinding * &denominator;
+
+        let commitment_in = PedersenCommitment::commit(amount_in, &blinding_in, &gens);
+        let commitment_out_plus_fee =
+            PedersenCommitment::commit(amount_out + fee, &blinding_out, &fee_gens);
+
+        assert!(verify_conservation(&commitment_in, &commitment_out_plus_fee, ratio));
+
+        // Independently-sampled blinding factors almost never balance.
+        let unbalanced = PedersenCommitment::commit(amount_out + fee, &sample_blinding(), &fee_gens);
+        assert!(!verify_conservation(&commitment_in, &unbalanced, ratio));
+    }
+
     #[test]
     fn test_satoshi_constant() {
         // Verify η = λ = 1/√2
@@ -349,4 +587,42 @@ mod tests {
         let expected = tau / 2.0_f64.sqrt();
         assert!((phase - expected).abs() < 1e-10);
     }
+
+    fn liquidity(pool: DimensionalPool, amount: Balance, dimensional_factor: f64) -> PoolLiquidity {
+        PoolLiquidity {
+            pool,
+            liquidity: amount,
+            dimensional_factor,
+            allocation_ratio: 0.0,
+            tau: 0.0,
+            phase: 0.0,
+            last_update_height: 0,
+        }
+    }
+
+    #[test]
+    fn test_quote_matches_constant_product_formula() {
+        let from = liquidity(DimensionalPool::D1, 1_000_000, 1.0);
+        let to = liquidity(DimensionalPool::D2, 1_000_000, 0.867);
+
+        let quote = DimensionalPoolState::quote_from_liquidity(&from, &to, 10_000);
+
+        let x = 1_000_000.0;
+        let y = 1_000_000.0 * 0.867;
+        let expected_out = ((y * 10_000.0) / (x + 10_000.0)) as Balance;
+        assert_eq!(quote.amount_out, expected_out);
+        assert!((quote.marginal_price - y / x).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_larger_trades_incur_more_price_impact() {
+        let from = liquidity(DimensionalPool::D1, 1_000_000, 1.0);
+        let to = liquidity(DimensionalPool::D2, 1_000_000, 0.867);
+
+        let small = DimensionalPoolState::quote_from_liquidity(&from, &to, 1_000);
+        let large = DimensionalPoolState::quote_from_liquidity(&from, &to, 500_000);
+
+        assert!(large.price_impact > small.price_impact);
+        assert!(large.execution_price < large.marginal_price);
+    }
 }
@@ -7,6 +7,7 @@ pub mod escrows;
 pub mod channels;
 pub mod trustlines;
 pub mod dimensional_pools;
+pub mod confidential;
 
 pub use accounts::*;
 pub use timelocks::*;
@@ -14,3 +15,4 @@ pub use escrows::*;
 pub use channels::*;
 pub use trustlines::*;
 pub use dimensional_pools::*;
+pub use confidential::*;
@@ -2,10 +2,15 @@
 // block_reward = base_constant × (work_score / epoch_average_work)
 
 use coinject_core::{Balance, WorkScore};
+use consensus::{Difficulty, EpochWorkTracker};
+
+/// 120-block retarget window at a 10s target block spacing.
+const DEFAULT_TARGET_TIMESPAN_SECS: i64 = 120 * 10;
 
 pub struct RewardCalculator {
     base_constant: f64,
     epoch_average_work: f64,
+    work_tracker: EpochWorkTracker,
 }
 
 impl RewardCalculator {
@@ -13,6 +18,7 @@ impl RewardCalculator {
         RewardCalculator {
             base_constant: 10_000_000.0, // 10 million base reward for testing
             epoch_average_work: 1.0,
+            work_tracker: EpochWorkTracker::new(DEFAULT_TARGET_TIMESPAN_SECS),
         }
     }
 
@@ -26,6 +32,21 @@ impl RewardCalculator {
     pub fn update_epoch_average(&mut self, average_work: f64) {
         self.epoch_average_work = average_work;
     }
+
+    /// Record a newly mined block's timestamp and work score, then
+    /// recompute the epoch average and the retargeted difficulty from the
+    /// same sliding window so reward scaling and difficulty never drift
+    /// apart within an epoch.
+    pub fn record_block(&mut self, timestamp: i64, work_score: f64) -> Difficulty {
+        self.work_tracker.record_block(timestamp, work_score);
+        self.epoch_average_work = self.work_tracker.average_work();
+        self.work_tracker.retarget_difficulty()
+    }
+
+    /// Current retargeted difficulty.
+    pub fn current_difficulty(&self) -> Difficulty {
+        self.work_tracker.current_difficulty()
+    }
 }
 
 #[cfg(test)]